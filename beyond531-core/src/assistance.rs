@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::Unit;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum MovementPattern {
+    Push,
+    Pull,
+    SingleLeg,
+    Core,
+}
+
+impl MovementPattern {
+    pub const ALL: [MovementPattern; 4] = [
+        MovementPattern::Push,
+        MovementPattern::Pull,
+        MovementPattern::SingleLeg,
+        MovementPattern::Core,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            MovementPattern::Push => "Push",
+            MovementPattern::Pull => "Pull",
+            MovementPattern::SingleLeg => "Single leg",
+            MovementPattern::Core => "Core",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LibraryExercise {
+    pub name: &'static str,
+    pub pattern: MovementPattern,
+}
+
+const fn exercise(name: &'static str, pattern: MovementPattern) -> LibraryExercise {
+    LibraryExercise { name, pattern }
+}
+
+/// Built-in assistance exercises, grouped by movement pattern.
+pub const LIBRARY: &[LibraryExercise] = &[
+    exercise("Dips", MovementPattern::Push),
+    exercise("Push-ups", MovementPattern::Push),
+    exercise("Dumbbell Bench Press", MovementPattern::Push),
+    exercise("Dumbbell Overhead Press", MovementPattern::Push),
+    exercise("Chin-ups", MovementPattern::Pull),
+    exercise("Dumbbell Rows", MovementPattern::Pull),
+    exercise("Face Pulls", MovementPattern::Pull),
+    exercise("Lat Pulldowns", MovementPattern::Pull),
+    exercise("Walking Lunges", MovementPattern::SingleLeg),
+    exercise("Bulgarian Split Squats", MovementPattern::SingleLeg),
+    exercise("Step-ups", MovementPattern::SingleLeg),
+    exercise("Single-leg RDLs", MovementPattern::SingleLeg),
+    exercise("Ab Wheel Rollouts", MovementPattern::Core),
+    exercise("Hanging Leg Raises", MovementPattern::Core),
+    exercise("Back Extensions", MovementPattern::Core),
+    exercise("Side Planks", MovementPattern::Core),
+];
+
+pub fn find_in_library(name: &str) -> Option<&'static LibraryExercise> {
+    LIBRARY.iter().find(|exercise| exercise.name == name)
+}
+
+/// Total reps per movement pattern a session should land in.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VolumeTarget {
+    pub min_reps: u32,
+    pub max_reps: u32,
+}
+
+impl Default for VolumeTarget {
+    fn default() -> Self {
+        Self {
+            min_reps: 50,
+            max_reps: 100,
+        }
+    }
+}
+
+/// One library exercise picked for a training day. `weight` is in the program's unit;
+/// `None` means bodyweight or whatever the lifter chooses.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AssistancePick {
+    pub day: String,
+    pub exercise: String,
+    pub sets: u32,
+    pub reps: u32,
+    pub weight: Option<f64>,
+}
+
+impl AssistancePick {
+    /// 5x10, which alone reaches the default volume target.
+    pub fn new(day: &str, exercise: &LibraryExercise) -> Self {
+        Self {
+            day: day.to_string(),
+            exercise: exercise.name.to_string(),
+            sets: 5,
+            reps: 10,
+            weight: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct AssistancePlan {
+    pub picks: Vec<AssistancePick>,
+    pub targets: HashMap<MovementPattern, VolumeTarget>,
+}
+
+impl AssistancePlan {
+    pub fn target_for(&self, pattern: MovementPattern) -> VolumeTarget {
+        self.targets.get(&pattern).copied().unwrap_or_default()
+    }
+
+    /// The assistance listed under a session on `day`; picks no longer in the library are skipped.
+    pub fn work_for(&self, day: &str) -> Vec<AssistanceWork> {
+        self.picks
+            .iter()
+            .filter(|pick| pick.day == day)
+            .filter_map(|pick| {
+                let exercise = find_in_library(&pick.exercise)?;
+                Some(AssistanceWork {
+                    name: exercise.name.to_string(),
+                    pattern: exercise.pattern,
+                    sets: pick.sets,
+                    reps: pick.reps,
+                    weight: pick.weight,
+                    target: self.target_for(exercise.pattern),
+                })
+            })
+            .collect()
+    }
+}
+
+/// An assistance exercise as prescribed in a generated session.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct AssistanceWork {
+    pub name: String,
+    pub pattern: MovementPattern,
+    pub sets: u32,
+    pub reps: u32,
+    pub weight: Option<f64>,
+    pub target: VolumeTarget,
+}
+
+impl AssistanceWork {
+    /// One-line prescription, e.g. "Dips: 5x10 @ 10kg".
+    pub fn describe(&self, unit: Unit) -> String {
+        let weight = self.weight.map(|weight| format!(" @ {}{}", weight, unit)).unwrap_or_default();
+        format!("{}: {}x{}{}", self.name, self.sets, self.reps, weight)
+    }
+}
+
+/// Reps per movement pattern across a session's assistance, in `MovementPattern::ALL` order.
+pub fn volume_by_pattern(work: &[AssistanceWork]) -> Vec<(MovementPattern, u32, VolumeTarget)> {
+    MovementPattern::ALL
+        .into_iter()
+        .filter_map(|pattern| {
+            let mut exercises = work.iter().filter(|work| work.pattern == pattern).peekable();
+            let target = exercises.peek()?.target;
+            Some((pattern, exercises.map(|work| work.sets * work.reps).sum(), target))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn lists_picks_for_their_day_only() {
+        let plan = AssistancePlan {
+            picks: vec![
+                AssistancePick::new("Monday", &LIBRARY[0]),
+                AssistancePick::new("Friday", &LIBRARY[4]),
+            ],
+            ..Default::default()
+        };
+
+        let monday = plan.work_for("Monday");
+
+        assert_eq!(monday.len(), 1);
+        assert_eq!((monday[0].name.as_str(), monday[0].pattern), ("Dips", MovementPattern::Push));
+    }
+
+    #[test]
+    fn sums_volume_per_pattern_against_its_target() {
+        let mut plan = AssistancePlan::default();
+        plan.targets.insert(MovementPattern::Pull, VolumeTarget { min_reps: 25, max_reps: 50 });
+        plan.picks.push(AssistancePick::new("Friday", find_in_library("Chin-ups").unwrap()));
+        plan.picks.push(AssistancePick {
+            sets: 3,
+            reps: 15,
+            ..AssistancePick::new("Friday", find_in_library("Face Pulls").unwrap())
+        });
+        plan.picks.push(AssistancePick::new("Friday", find_in_library("Ab Wheel Rollouts").unwrap()));
+
+        let volume = volume_by_pattern(&plan.work_for("Friday"));
+
+        assert_eq!(
+            volume,
+            vec![
+                (MovementPattern::Pull, 95, VolumeTarget { min_reps: 25, max_reps: 50 }),
+                (MovementPattern::Core, 50, VolumeTarget::default()),
+            ]
+        );
+    }
+}
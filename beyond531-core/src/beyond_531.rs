@@ -0,0 +1,552 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use chrono::NaiveDate;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::assistance::{AssistancePlan, AssistanceWork};
+use crate::estimated_max::{E1rmFormula, SetKey, TrainingLog};
+use crate::joker::StopRule;
+use crate::program_template::{ProgramTemplate, SessionTemplate};
+use crate::rounding::RoundingPolicy;
+use crate::rpe::{self, Autoregulation};
+use crate::schedule;
+use crate::warmup::WarmupSettings;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Unit {
+    #[default]
+    Kilograms,
+    Pounds,
+}
+
+impl Unit {
+    pub const KG_PER_LB: f64 = 0.45359237;
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Unit::Kilograms => "kg",
+            Unit::Pounds => "lb",
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Unit::Kilograms => "kilograms",
+            Unit::Pounds => "pounds",
+        }
+    }
+
+    /// Smallest jump a standard plate set allows: 2 x 1.25 kg or 2 x 2.5 lb.
+    pub fn rounding_increment(&self) -> f64 {
+        match self {
+            Unit::Kilograms => 2.5,
+            Unit::Pounds => 5.0,
+        }
+    }
+
+    pub fn convert(&self, weight: f64, to: Unit) -> f64 {
+        match (self, to) {
+            (Unit::Kilograms, Unit::Pounds) => weight / Self::KG_PER_LB,
+            (Unit::Pounds, Unit::Kilograms) => weight * Self::KG_PER_LB,
+            _ => weight,
+        }
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
+
+/// Identifies a lift independently of its name, so renaming it keeps its settings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LiftId(pub u32);
+
+/// Decides how a lift is programmed: hinges get a single Monday set and
+/// lower body lifts progress twice as fast as presses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum LiftCategory {
+    #[default]
+    Squat,
+    Hinge,
+    Press,
+}
+
+impl LiftCategory {
+    pub const ALL: [LiftCategory; 3] = [LiftCategory::Squat, LiftCategory::Hinge, LiftCategory::Press];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            LiftCategory::Squat => "Squat",
+            LiftCategory::Hinge => "Hinge",
+            LiftCategory::Press => "Press",
+        }
+    }
+
+    /// Short lowercase form used in links and on the command line.
+    pub fn token(&self) -> &'static str {
+        match self {
+            LiftCategory::Squat => "squat",
+            LiftCategory::Hinge => "hinge",
+            LiftCategory::Press => "press",
+        }
+    }
+
+    pub fn from_token(token: &str) -> Option<LiftCategory> {
+        LiftCategory::ALL.into_iter().find(|category| category.token() == token)
+    }
+
+    pub fn is_lower_body(&self) -> bool {
+        matches!(self, LiftCategory::Squat | LiftCategory::Hinge)
+    }
+
+    /// The classic +5/+10 lb (+2.5/+5 kg) jumps for upper/lower body lifts.
+    pub fn default_increment(&self, unit: Unit) -> f64 {
+        if self.is_lower_body() {
+            unit.rounding_increment() * 2.0
+        } else {
+            unit.rounding_increment()
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Lift {
+    pub id: LiftId,
+    pub name: String,
+    pub category: LiftCategory,
+}
+
+impl Lift {
+    pub fn new(id: u32, name: &str, category: LiftCategory) -> Self {
+        Self {
+            id: LiftId(id),
+            name: name.to_string(),
+            category,
+        }
+    }
+
+    /// The Beyond 531 trio: front squat, deadlift and bench press.
+    pub fn defaults() -> Vec<Lift> {
+        vec![
+            Lift::new(1, "Front Squat", LiftCategory::Squat),
+            Lift::new(2, "Deadlift", LiftCategory::Hinge),
+            Lift::new(3, "Bench Press", LiftCategory::Press),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LiftMax {
+    pub lift: Lift,
+    pub weight: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct OneRepMax {
+    pub lifts: Vec<LiftMax>,
+    pub unit: Unit,
+}
+
+impl OneRepMax {
+    pub fn converted_to(&self, unit: Unit) -> OneRepMax {
+        OneRepMax {
+            lifts: self
+                .lifts
+                .iter()
+                .map(|max| LiftMax {
+                    lift: max.lift.clone(),
+                    weight: self.unit.convert(max.weight, unit),
+                })
+                .collect(),
+            unit,
+        }
+    }
+
+    pub fn for_lift(&self, id: LiftId) -> Option<f64> {
+        self.lifts.iter().find(|max| max.lift.id == id).map(|max| max.weight)
+    }
+}
+
+/// A working max taken as a fraction of the true 1RM, as in standard 5/3/1.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TrainingMax {
+    pub factor: f64,
+}
+
+impl Default for TrainingMax {
+    fn default() -> Self {
+        Self { factor: 0.9 }
+    }
+}
+
+impl TrainingMax {
+    pub fn of(&self, one_rm: f64) -> f64 {
+        one_rm * self.factor
+    }
+}
+
+/// The number template percentages are taken from.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum MaxBasis {
+    #[default]
+    OneRepMax,
+    TrainingMax(TrainingMax),
+}
+
+impl MaxBasis {
+    pub fn base_for(&self, one_rm: f64) -> f64 {
+        match self {
+            MaxBasis::OneRepMax => one_rm,
+            MaxBasis::TrainingMax(training_max) => training_max.of(one_rm),
+        }
+    }
+}
+
+/// How many cycles to generate and how much each lift's 1RM goes up between them.
+/// With a training max basis the TM follows the bumped 1RM. A lift with logged AMRAP
+/// results instead carries its best estimated 1RM into the next cycle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progression {
+    pub cycles: u32,
+    pub increments: HashMap<LiftId, f64>,
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Self::for_lifts(&Lift::defaults(), Unit::default())
+    }
+}
+
+impl Progression {
+    /// One cycle, with each lift's category default increment.
+    pub fn for_lifts(lifts: &[Lift], unit: Unit) -> Self {
+        Self {
+            cycles: 1,
+            increments: lifts
+                .iter()
+                .map(|lift| (lift.id, lift.category.default_increment(unit)))
+                .collect(),
+        }
+    }
+
+    pub fn increment_for(&self, id: LiftId) -> f64 {
+        self.increments.get(&id).copied().unwrap_or(0.0)
+    }
+
+    pub fn next_cycle(&self, one_rep_max: &OneRepMax, estimated: &HashMap<LiftId, f64>) -> OneRepMax {
+        let lifts = one_rep_max
+            .lifts
+            .iter()
+            .map(|max| LiftMax {
+                lift: max.lift.clone(),
+                weight: match estimated.get(&max.lift.id) {
+                    Some(&estimate) => (estimate * 10.0).round() / 10.0,
+                    None => max.weight + self.increment_for(max.lift.id),
+                },
+            })
+            .collect();
+
+        OneRepMax {
+            lifts,
+            unit: one_rep_max.unit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ProgramSettings {
+    pub template: ProgramTemplate,
+    pub basis: MaxBasis,
+    pub rounding: RoundingPolicy,
+    pub progression: Progression,
+    pub e1rm_formula: E1rmFormula,
+    /// Prepends a warm-up ramp to each lift's work sets when set.
+    pub warmups: Option<WarmupSettings>,
+    pub assistance: AssistancePlan,
+    /// The first day of the first training week.
+    pub start_date: NaiveDate,
+    /// Prescribes AMRAP top sets as reps @ RPE instead when set.
+    pub autoregulation: Option<Autoregulation>,
+}
+
+/// What a set is for; main sets drive the AMRAP log, the rest are extra volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SetKind {
+    Warmup,
+    #[default]
+    Main,
+    Joker,
+    Supplemental,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Exercise {
+    pub name: String,
+    pub lift: LiftId,
+    pub kind: SetKind,
+    pub sets: u32,
+    pub reps: u32,
+    pub weight: f64,
+    pub percentage: f64,
+    pub is_amrap: bool,
+    pub stop_rule: Option<StopRule>,
+    /// Target effort for sets prescribed by RPE; `percentage` is then of the current max.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub rpe: Option<f64>,
+}
+
+impl Exercise {
+    /// One-line prescription, e.g. "Deadlift: 1x5+ @ 120kg (80%)".
+    pub fn describe(&self, unit: Unit) -> String {
+        let intensity = self.intensity();
+        if self.rpe.is_some() {
+            format!("{}: {}x{} @ {} ({}{})", self.name, self.sets, self.reps, intensity, self.weight, unit)
+        } else if self.is_amrap && self.sets == 1 && self.reps == 1 {
+            format!("{}: 1+ @ {}{} ({})", self.name, self.weight, unit, intensity)
+        } else if self.is_amrap {
+            format!("{}: {}x{}+ @ {}{} ({})", self.name, self.sets, self.reps, self.weight, unit, intensity)
+        } else if self.sets == 1 && self.reps == 1 {
+            format!("{}: {}{} ({})", self.name, self.weight, unit, intensity)
+        } else {
+            format!("{}: {}x{} @ {}{} ({})", self.name, self.sets, self.reps, self.weight, unit, intensity)
+        }
+    }
+
+    /// "RPE 8" for sets prescribed by RPE, otherwise the percentage to the nearest whole one.
+    pub fn intensity(&self) -> String {
+        match self.rpe {
+            Some(rpe) => format!("RPE {}", rpe),
+            None => format!("{}%", self.percentage.round()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Session {
+    pub day: String,
+    pub date: NaiveDate,
+    pub exercises: Vec<Exercise>,
+    /// Listed after the barbell work; never part of the AMRAP log.
+    pub assistance: Vec<AssistanceWork>,
+}
+
+/// What a week of the block is for, which decides how it's labelled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WeekKind {
+    #[default]
+    Training,
+    Max,
+    Deload,
+    TrainingMaxTest,
+}
+
+impl WeekKind {
+    pub fn label(&self) -> Option<&'static str> {
+        match self {
+            WeekKind::Training => None,
+            WeekKind::Max => Some("MAX WEEK!"),
+            WeekKind::Deload => Some("Deload"),
+            WeekKind::TrainingMaxTest => Some("TM Test"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Week {
+    pub week_number: u32,
+    // Files exported before weeks had a kind load as plain training weeks
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub kind: WeekKind,
+    pub sessions: Vec<Session>,
+}
+
+impl Week {
+    pub fn title(&self) -> String {
+        match self.kind.label() {
+            Some(label) => format!("Week {} - {}", self.week_number, label),
+            None => format!("Week {}", self.week_number),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Cycle {
+    pub cycle_number: u32,
+    /// The maxes this cycle's weights were calculated from.
+    pub one_rep_max: OneRepMax,
+    pub weeks: Vec<Week>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct TrainingProgram {
+    pub cycles: Vec<Cycle>,
+    pub unit: Unit,
+}
+
+pub struct Beyond531Calculator;
+
+impl Beyond531Calculator {
+    /// AMRAP results in `log` for one cycle set the next cycle's maxes; pass an empty log
+    /// to progress purely by the configured increments.
+    pub fn calculate_program(
+        one_rep_max: &OneRepMax,
+        settings: &ProgramSettings,
+        log: &TrainingLog,
+    ) -> TrainingProgram {
+        let mut cycles: Vec<Cycle> = Vec::new();
+        let mut cycle_max = one_rep_max.clone();
+
+        for cycle_number in 1..=settings.progression.cycles.max(1) {
+            if let Some(previous) = cycles.last() {
+                let estimated = log.best_estimates(previous.cycle_number, &previous.weeks, settings.e1rm_formula);
+                cycle_max = settings.progression.next_cycle(&cycle_max, &estimated);
+            }
+            cycles.push(Cycle {
+                cycle_number,
+                weeks: Self::calculate_weeks(cycle_number, &cycle_max, settings, log),
+                one_rep_max: cycle_max.clone(),
+            });
+        }
+
+        TrainingProgram {
+            cycles,
+            unit: one_rep_max.unit,
+        }
+    }
+
+    fn calculate_weeks(
+        cycle_number: u32,
+        one_rep_max: &OneRepMax,
+        settings: &ProgramSettings,
+        log: &TrainingLog,
+    ) -> Vec<Week> {
+        let weeks_per_cycle = settings.template.weeks.len() as u32;
+        let first_day = settings
+            .template
+            .weeks
+            .iter()
+            .flat_map(|week| &week.sessions)
+            .map(|session| session.weekday)
+            .min()
+            .unwrap_or(schedule::Weekday::Monday);
+        // Each lift's max for RPE top sets, moved by the RPE logged on earlier ones
+        let mut current_maxes = HashMap::new();
+        let mut weeks = Vec::new();
+
+        let last_cycle = cycle_number == settings.progression.cycles.max(1);
+        for (week_index, week) in settings.template.weeks.iter().enumerate() {
+            if week.between_cycles && last_cycle {
+                continue;
+            }
+            let week_offset = (cycle_number - 1) * weeks_per_cycle + week_index as u32;
+            let mut sessions = Vec::new();
+
+            for (session_index, session) in week.sessions.iter().enumerate() {
+                let date = schedule::session_date(settings.start_date, first_day, week_offset, session.weekday);
+                let session = Self::calculate_session(one_rep_max, &current_maxes, settings, session, date);
+
+                let keys = SetKey::for_session(cycle_number, week.week_number, session_index, &session);
+                for (exercise, key) in session.exercises.iter().zip(keys) {
+                    let logged = key.and_then(|key| log.rpe_for(&key));
+                    if let Some(estimate) = logged.and_then(|rpe| rpe::estimate_max(exercise.weight, exercise.reps, rpe)) {
+                        current_maxes.insert(exercise.lift, estimate);
+                    }
+                }
+                sessions.push(session);
+            }
+
+            weeks.push(Week {
+                week_number: week.week_number,
+                kind: week.kind,
+                sessions,
+            });
+        }
+
+        weeks
+    }
+
+    fn calculate_session(
+        one_rep_max: &OneRepMax,
+        current_maxes: &HashMap<LiftId, f64>,
+        settings: &ProgramSettings,
+        session: &SessionTemplate,
+        date: NaiveDate,
+    ) -> Session {
+        let mut exercises = Vec::new();
+
+        for lift_template in &session.lifts {
+            let lift = &lift_template.lift;
+            let Some(one_rm) = one_rep_max.for_lift(lift.id) else {
+                continue;
+            };
+            let base = settings.basis.base_for(one_rm);
+            let current_max = current_maxes.get(&lift.id).copied().unwrap_or(one_rm);
+
+            let mut lift_exercises: Vec<Exercise> = lift_template.sets.iter().map(|set| {
+                let from = if set.of_one_rep_max { one_rm } else { base };
+                let name = match &set.label {
+                    Some(label) => format!("{} - {}", lift.name, label),
+                    None => lift.name.clone(),
+                };
+                let rpe_top_set = settings
+                    .autoregulation
+                    .filter(|_| set.is_amrap && set.kind == SetKind::Main)
+                    .and_then(|autoregulation| {
+                        let rpe = autoregulation.top_set_rpe;
+                        Some((rpe, rpe::percentage(set.reps, rpe)?))
+                    });
+
+                let (weight, percentage, is_amrap, rpe) = match rpe_top_set {
+                    Some((rpe, percentage)) => (current_max * (percentage / 100.0), percentage, false, Some(rpe)),
+                    None => (from * (set.percentage / 100.0), set.percentage, set.is_amrap, None),
+                };
+
+                Exercise {
+                    name,
+                    lift: lift.id,
+                    kind: set.kind,
+                    sets: set.sets,
+                    reps: set.reps,
+                    weight: settings.rounding.round(lift.id, weight),
+                    percentage,
+                    is_amrap,
+                    stop_rule: set.stop_rule,
+                    rpe,
+                }
+            }).collect();
+
+            // The ramp leads into the first set, so it runs up to a template's own warm-up
+            // set (the max week's 65% single) rather than past it
+            let first_set = lift_exercises.first().map(|exercise| exercise.weight);
+            if let (Some(warmups), Some(first_set)) = (&settings.warmups, first_set) {
+                let round = |weight| settings.rounding.round(lift.id, weight);
+                lift_exercises.splice(0..0, warmups.exercises(lift, base, first_set, round));
+            }
+            exercises.extend(lift_exercises);
+        }
+
+        Session {
+            day: session.day.clone(),
+            date,
+            exercises,
+            assistance: settings.assistance.work_for(&session.day),
+        }
+    }
+}
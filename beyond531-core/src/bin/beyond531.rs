@@ -0,0 +1,349 @@
+//! Generates a Beyond 531 program from the command line, for scripting programs for a roster.
+
+use std::env;
+use std::process::ExitCode;
+
+use chrono::{Local, NaiveDate};
+
+use beyond531_core::beyond_531::{Beyond531Calculator, Lift, LiftCategory, TrainingProgram, Unit};
+use beyond531_core::block::BlockStructure;
+use beyond531_core::calculator_state::{CalculatorState, LiftEntry};
+use beyond531_core::estimated_max::TrainingLog;
+use beyond531_core::program_file;
+use beyond531_core::rpe::{self, Autoregulation};
+use beyond531_core::schedule::Schedule;
+use beyond531_core::supplemental::SupplementalTemplate;
+use beyond531_core::validation::{self, parse_number};
+
+const USAGE: &str = "\
+Usage: beyond531 --lift CATEGORY:MAX[:NAME]... [options]
+
+Prints a Beyond 531 program to stdout.
+
+Options:
+  --lift CATEGORY:MAX[:NAME]  A lift and its 1RM; CATEGORY is squat, hinge or press.
+                              Repeat for each lift. NAME defaults to the category.
+  --unit kg|lb                Unit the maxes are in (default kg)
+  --template NAME             Supplemental work for every lift: standard (none), bbb,
+                              fsl, ssl or widowmaker (default standard)
+  --schedule two-day|three-day
+                              Monday/Friday, or Monday/Wednesday/Friday rotating
+                              (default two-day)
+  --training-max PERCENT      Base percentages on a training max of PERCENT (50 to 100)
+                              of the 1RM
+  --rpe RPE                   Prescribe top sets as reps @ RPE (6 to 10 in half steps)
+                              instead of AMRAP sets
+  --cycles N                  Number of cycles to generate (default 1)
+  --block NAME                What follows each max week: none, deload (40/50/60% x5),
+                              tm-test or seventh-week-deload (default none). The
+                              7th-week options run between cycles and need
+                              --training-max
+  --start YYYY-MM-DD          Date of the first training day (default today)
+  --format table|json|csv     Output format (default table); JSON can be imported
+                              into the web calculator
+  -h, --help                  Show this message
+";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Format {
+    Table,
+    Json,
+    Csv,
+}
+
+#[derive(Debug)]
+struct Options {
+    state: CalculatorState,
+    format: Format,
+}
+
+fn main() -> ExitCode {
+    let options = match parse_args(env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            print!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(err) => {
+            eprintln!("beyond531: {}\n\n{}", err, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let state = options.state;
+    if let Some(reason) = state.generate_blocker() {
+        eprintln!("beyond531: {}", reason);
+        return ExitCode::FAILURE;
+    }
+
+    let log = TrainingLog::default();
+    let program = Beyond531Calculator::calculate_program(&state.one_rep_max(), &state.program_settings(), &log);
+    match options.format {
+        Format::Table => print!("{}", render_table(&program)),
+        Format::Json => println!("{}", program_file::to_json(&state, &log, &program)),
+        Format::Csv => print!("{}", program_file::to_csv(&program)),
+    }
+    ExitCode::SUCCESS
+}
+
+/// `Ok(None)` when help was asked for.
+fn parse_args(args: impl IntoIterator<Item = String>) -> Result<Option<Options>, String> {
+    let mut args = args.into_iter();
+    let mut lifts: Vec<(LiftCategory, String, String)> = Vec::new();
+    let mut unit = Unit::Kilograms;
+    let mut template = None;
+    let mut three_day = false;
+    let mut training_max = None;
+    let mut autoregulation = None;
+    let mut cycles = 1;
+    let mut block = BlockStructure::MaxWeekOnly;
+    let mut start = None;
+    let mut format = Format::Table;
+
+    while let Some(flag) = args.next() {
+        if flag == "-h" || flag == "--help" {
+            return Ok(None);
+        }
+        let mut value = || args.next().ok_or_else(|| format!("{} needs a value", flag));
+
+        match flag.as_str() {
+            "--lift" => lifts.push(parse_lift(&value()?)?),
+            "--unit" => {
+                unit = match value()?.as_str() {
+                    "kg" => Unit::Kilograms,
+                    "lb" => Unit::Pounds,
+                    other => return Err(format!("unknown unit \"{}\" (expected kg or lb)", other)),
+                }
+            }
+            "--template" => {
+                template = match value()?.as_str() {
+                    "standard" => None,
+                    "bbb" => Some(SupplementalTemplate::BoringButBig),
+                    "fsl" => Some(SupplementalTemplate::FirstSetLast),
+                    "ssl" => Some(SupplementalTemplate::SecondSetLast),
+                    "widowmaker" => Some(SupplementalTemplate::Widowmaker),
+                    other => return Err(format!("unknown template \"{}\"", other)),
+                }
+            }
+            "--schedule" => {
+                three_day = match value()?.as_str() {
+                    "two-day" => false,
+                    "three-day" => true,
+                    other => return Err(format!("unknown schedule \"{}\"", other)),
+                }
+            }
+            "--training-max" => {
+                let percent = parse_number(&value()?)
+                    .and_then(|percent| validation::check_training_max_percent(percent).map(|_| percent))
+                    .map_err(|err| format!("--training-max: {}", err))?;
+                training_max = Some(percent);
+            }
+            "--rpe" => {
+                let text = value()?;
+                let top_set_rpe = parse_number(&text)
+                    .ok()
+                    .filter(|rpe| rpe::RPE_OPTIONS.contains(rpe))
+                    .ok_or_else(|| format!("--rpe must be 6 to 10 in half steps, got \"{}\"", text))?;
+                autoregulation = Some(Autoregulation { top_set_rpe });
+            }
+            "--cycles" => {
+                let text = value()?;
+                cycles = text
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|cycles| *cycles > 0)
+                    .ok_or_else(|| format!("--cycles must be a positive whole number, got \"{}\"", text))?;
+            }
+            "--block" => {
+                block = match value()?.as_str() {
+                    "none" => BlockStructure::MaxWeekOnly,
+                    "deload" => BlockStructure::DeloadAfterMaxWeek,
+                    "tm-test" => BlockStructure::SeventhWeekTrainingMaxTest,
+                    "seventh-week-deload" => BlockStructure::SeventhWeekDeload,
+                    other => return Err(format!("unknown block \"{}\"", other)),
+                }
+            }
+            "--start" => {
+                let text = value()?;
+                let date = NaiveDate::parse_from_str(&text, "%Y-%m-%d")
+                    .map_err(|_| format!("--start must be a date like 2026-01-05, got \"{}\"", text))?;
+                start = Some(date);
+            }
+            "--format" => {
+                format = match value()?.as_str() {
+                    "table" => Format::Table,
+                    "json" => Format::Json,
+                    "csv" => Format::Csv,
+                    other => return Err(format!("unknown format \"{}\"", other)),
+                }
+            }
+            other => return Err(format!("unknown option \"{}\"", other)),
+        }
+    }
+
+    if lifts.is_empty() {
+        return Err("at least one --lift is required".to_string());
+    }
+
+    let mut state = CalculatorState::for_unit(unit);
+    state.lifts = Vec::new();
+    for (id, (category, max, name)) in (1..).zip(lifts) {
+        let mut entry = LiftEntry::new(Lift::new(id, &name, category), unit);
+        entry.one_rep_max = parse_number(&max).map_err(|err| format!("{}: {}", name, err))?;
+        state.lifts.push(entry);
+    }
+    if let Some(template) = template {
+        state.supplemental.templates = state.lifts.iter().map(|entry| (entry.lift.id, template)).collect();
+    }
+    if three_day {
+        let lifts: Vec<Lift> = state.lifts.iter().map(|entry| entry.lift.clone()).collect();
+        state.schedule = Schedule::three_day_rotating(&lifts);
+    }
+    if let Some(percent) = training_max {
+        state.use_training_max = true;
+        state.training_max_percent = percent;
+    }
+    state.autoregulation = autoregulation;
+    state.cycles = cycles;
+    if block.needs_training_max() && training_max.is_none() {
+        return Err("--block tm-test and seventh-week-deload need --training-max".to_string());
+    }
+    state.block = block;
+    state.calendar.start_date = Some(start.unwrap_or_else(|| Local::now().date_naive()));
+    state.program_generated = true;
+
+    Ok(Some(Options { state, format }))
+}
+
+/// `CATEGORY:MAX[:NAME]`; the name goes last so it may contain `:` itself.
+fn parse_lift(spec: &str) -> Result<(LiftCategory, String, String), String> {
+    let mut parts = spec.splitn(3, ':');
+    let (Some(category), Some(max)) = (parts.next(), parts.next()) else {
+        return Err(format!("\"{}\" is not a lift (expected CATEGORY:MAX[:NAME])", spec));
+    };
+    let category = LiftCategory::from_token(category)
+        .ok_or_else(|| format!("unknown lift category \"{}\" (expected squat, hinge or press)", category))?;
+    let name = parts.next().filter(|name| !name.is_empty()).unwrap_or(category.name());
+
+    Ok((category, max.to_string(), name.to_string()))
+}
+
+/// Plain-text tables, one per week, with the columns padded to line up.
+fn render_table(program: &TrainingProgram) -> String {
+    let mut output = String::new();
+
+    for cycle in &program.cycles {
+        output.push_str(&format!(
+            "Cycle {} ({})\n\n",
+            cycle.cycle_number,
+            cycle
+                .one_rep_max
+                .lifts
+                .iter()
+                .map(|max| format!("{} {}{}", max.lift.name, max.weight, program.unit))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+
+        for week in &cycle.weeks {
+            let mut rows = vec![["Day".to_string(), "Exercise".to_string(), "Sets x Reps".to_string(), "Weight".to_string(), "%".to_string()]];
+            for session in &week.sessions {
+                let day = format!("{} {}", session.day, session.date);
+                for (index, exercise) in session.exercises.iter().enumerate() {
+                    let plus = if exercise.is_amrap { "+" } else { "" };
+                    let intensity = exercise.intensity();
+                    rows.push([
+                        if index == 0 { day.clone() } else { String::new() },
+                        exercise.name.clone(),
+                        format!("{}x{}{}", exercise.sets, exercise.reps, plus),
+                        format!("{}{}", exercise.weight, program.unit),
+                        intensity,
+                    ]);
+                }
+                for work in &session.assistance {
+                    rows.push([
+                        String::new(),
+                        work.name.clone(),
+                        format!("{}x{}", work.sets, work.reps),
+                        work.weight.map(|weight| format!("{}{}", weight, program.unit)).unwrap_or_default(),
+                        String::new(),
+                    ]);
+                }
+            }
+
+            let widths: Vec<usize> = (0..5)
+                .map(|column| rows.iter().map(|row| row[column].chars().count()).max().unwrap_or(0))
+                .collect();
+            output.push_str(&week.title());
+            output.push('\n');
+            for row in &rows {
+                let cells: Vec<String> = row
+                    .iter()
+                    .zip(&widths)
+                    .map(|(cell, width)| format!("{:<width$}", cell, width = width))
+                    .collect();
+                output.push_str(cells.join("  ").trim_end());
+                output.push('\n');
+            }
+            output.push('\n');
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split(' ').map(str::to_string).collect()
+    }
+
+    #[test]
+    fn builds_inputs_from_flags() {
+        let options = parse_args(args("--lift squat:140 --lift press:100:Bench --unit lb --template bbb --cycles 2 --format csv --start 2026-01-05 --rpe 8.5 --block deload"))
+            .unwrap()
+            .unwrap();
+
+        let state = options.state;
+        assert_eq!(options.format, Format::Csv);
+        assert_eq!(state.unit, Unit::Pounds);
+        assert_eq!(state.cycles, 2);
+        assert_eq!(state.lifts[0].lift.name, "Squat");
+        assert_eq!((state.lifts[1].lift.name.as_str(), state.lifts[1].one_rep_max), ("Bench", 100.0));
+        assert_eq!(state.supplemental.templates.len(), 2);
+        assert_eq!(state.calendar.start_date, NaiveDate::from_ymd_opt(2026, 1, 5));
+        assert_eq!(state.autoregulation, Some(Autoregulation { top_set_rpe: 8.5 }));
+        assert_eq!(state.block, BlockStructure::DeloadAfterMaxWeek);
+    }
+
+    #[test]
+    fn rejects_bad_flags() {
+        assert_eq!(parse_args(args("--unit stone")).unwrap_err(), "unknown unit \"stone\" (expected kg or lb)");
+        assert_eq!(parse_args(args("--lift curl:50")).unwrap_err(), "unknown lift category \"curl\" (expected squat, hinge or press)");
+        assert_eq!(parse_args(args("--lift squat:abc")).unwrap_err(), "Squat: enter a number");
+        assert_eq!(parse_args(args("--cycles")).unwrap_err(), "--cycles needs a value");
+        assert_eq!(parse_args(args("--training-max 0")).unwrap_err(), "--training-max: must be between 50% and 100%");
+        assert_eq!(parse_args(args("--training-max 500")).unwrap_err(), "--training-max: must be between 50% and 100%");
+        assert_eq!(
+            parse_args(args("--lift squat:140 --block tm-test")).unwrap_err(),
+            "--block tm-test and seventh-week-deload need --training-max"
+        );
+        assert_eq!(parse_args(args("--rpe 8.25")).unwrap_err(), "--rpe must be 6 to 10 in half steps, got \"8.25\"");
+        assert!(parse_args(args("--help")).unwrap().is_none());
+    }
+
+    #[test]
+    fn table_lines_up_columns() {
+        let options = parse_args(args("--lift hinge:200:Deadlift --start 2026-01-05")).unwrap().unwrap();
+        let state = options.state;
+        let program = Beyond531Calculator::calculate_program(&state.one_rep_max(), &state.program_settings(), &TrainingLog::default());
+
+        let table = render_table(&program);
+
+        assert!(table.starts_with("Cycle 1 (Deadlift 200kg)\n\nWeek 1\nDay"));
+        assert!(table.contains("Monday 2026-01-05  Deadlift  1x5          130kg   65%\n"));
+        assert!(table.contains("                   Deadlift  1x5+         160kg   80%\n"));
+    }
+}
@@ -0,0 +1,168 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::{Lift, WeekKind};
+use crate::program_template::{LiftTemplate, ProgramTemplate, SessionTemplate, SetPrescription, WeekTemplate};
+use crate::schedule::Schedule;
+
+/// What follows the max week. The deload follows every cycle's max week; the 7th-week
+/// options only bridge one cycle into the next, so the last cycle goes without.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum BlockStructure {
+    /// Straight into the next cycle after the max week.
+    #[default]
+    MaxWeekOnly,
+    /// A light week of 40/50/60% x5 after the max week.
+    DeloadAfterMaxWeek,
+    /// The 7th-week protocol: work up to 100% of the training max for 5 to check it still fits.
+    SeventhWeekTrainingMaxTest,
+    /// The 7th-week protocol as a deload: 70%x5, 80%x3, then singles at 90% and 100%.
+    SeventhWeekDeload,
+}
+
+impl BlockStructure {
+    pub const ALL: [BlockStructure; 4] = [
+        BlockStructure::MaxWeekOnly,
+        BlockStructure::DeloadAfterMaxWeek,
+        BlockStructure::SeventhWeekTrainingMaxTest,
+        BlockStructure::SeventhWeekDeload,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            BlockStructure::MaxWeekOnly => "Max week, then the next cycle",
+            BlockStructure::DeloadAfterMaxWeek => "Deload week after the max week",
+            BlockStructure::SeventhWeekTrainingMaxTest => "7th week: training max test",
+            BlockStructure::SeventhWeekDeload => "7th week: deload",
+        }
+    }
+
+    /// The 7th-week sets are percentages of the training max, so they need one in use.
+    pub fn needs_training_max(&self) -> bool {
+        matches!(self, BlockStructure::SeventhWeekTrainingMaxTest | BlockStructure::SeventhWeekDeload)
+    }
+
+    fn between_cycles(&self) -> bool {
+        self.needs_training_max()
+    }
+
+    fn extra_week(&self) -> Option<(WeekKind, Vec<SetPrescription>)> {
+        match self {
+            BlockStructure::MaxWeekOnly => None,
+            BlockStructure::DeloadAfterMaxWeek => Some((
+                WeekKind::Deload,
+                vec![
+                    SetPrescription::new(1, 5, 40.0),
+                    SetPrescription::new(1, 5, 50.0),
+                    SetPrescription::new(1, 5, 60.0),
+                ],
+            )),
+            BlockStructure::SeventhWeekTrainingMaxTest => Some((
+                WeekKind::TrainingMaxTest,
+                vec![
+                    SetPrescription::new(1, 5, 70.0),
+                    SetPrescription::new(1, 5, 80.0),
+                    SetPrescription::new(1, 5, 90.0),
+                    SetPrescription::new(1, 5, 100.0).labelled("TM Test"),
+                ],
+            )),
+            BlockStructure::SeventhWeekDeload => Some((
+                WeekKind::Deload,
+                vec![
+                    SetPrescription::new(1, 5, 70.0),
+                    SetPrescription::new(1, 3, 80.0),
+                    SetPrescription::new(1, 1, 90.0),
+                    SetPrescription::new(1, 1, 100.0),
+                ],
+            )),
+        }
+    }
+
+    /// Appends the extra week to the cycle template. A deload trains each lift on all of its
+    /// usual days; the training max test only on its heavy day. Days left without a lift are
+    /// dropped.
+    pub fn apply_to(&self, template: &mut ProgramTemplate, lifts: &[Lift], schedule: &Schedule) {
+        let Some((kind, sets)) = self.extra_week() else {
+            return;
+        };
+        let week_number = template.weeks.iter().map(|week| week.week_number).max().unwrap_or(0) + 1;
+
+        let sessions = schedule
+            .days
+            .iter()
+            .enumerate()
+            .filter_map(|(day_index, weekday)| {
+                let on_this_day =
+                    |day: Option<usize>| day.map(|day| schedule.day_in_week(day, week_number)) == Some(day_index);
+                let lifts: Vec<LiftTemplate> = lifts
+                    .iter()
+                    .filter(|lift| {
+                        let days = schedule.lift_days(lift.id);
+                        on_this_day(days.heavy_day) || (kind == WeekKind::Deload && on_this_day(days.volume_day))
+                    })
+                    .map(|lift| LiftTemplate {
+                        lift: lift.clone(),
+                        sets: sets.clone(),
+                    })
+                    .collect();
+
+                (!lifts.is_empty()).then(|| SessionTemplate {
+                    day: weekday.name().to_string(),
+                    weekday: *weekday,
+                    lifts,
+                })
+            })
+            .collect();
+
+        template.weeks.push(WeekTemplate {
+            week_number,
+            kind,
+            sessions,
+            between_cycles: self.between_cycles(),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(block: BlockStructure) -> ProgramTemplate {
+        let lifts = Lift::defaults();
+        let schedule = Schedule::default();
+        let mut template = ProgramTemplate::beyond_531(&lifts, &schedule);
+        block.apply_to(&mut template, &lifts, &schedule);
+        template
+    }
+
+    #[test]
+    fn max_week_only_leaves_four_weeks() {
+        assert_eq!(template(BlockStructure::MaxWeekOnly), ProgramTemplate::default());
+    }
+
+    #[test]
+    fn deload_week_follows_the_max_week_on_every_training_day() {
+        let template = template(BlockStructure::DeloadAfterMaxWeek);
+
+        let week = template.weeks.last().unwrap();
+        assert_eq!((week.week_number, week.kind), (5, WeekKind::Deload));
+        let days: Vec<(&str, usize)> = week.sessions.iter().map(|session| (session.day.as_str(), session.lifts.len())).collect();
+        assert_eq!(days, vec![("Monday", 3), ("Friday", 3)]);
+        let percentages: Vec<f64> = week.sessions[0].lifts[0].sets.iter().map(|set| set.percentage).collect();
+        assert_eq!(percentages, vec![40.0, 50.0, 60.0]);
+    }
+
+    #[test]
+    fn seventh_week_test_works_up_to_the_training_max() {
+        let template = template(BlockStructure::SeventhWeekTrainingMaxTest);
+
+        let week = template.weeks.last().unwrap();
+        assert_eq!(week.kind, WeekKind::TrainingMaxTest);
+        assert_eq!(week.sessions.len(), 1);
+        assert_eq!(week.sessions[0].day, "Friday");
+        assert!(week.between_cycles);
+        let top_set = week.sessions[0].lifts[0].sets.last().unwrap();
+        assert_eq!((top_set.reps, top_set.percentage, top_set.of_one_rep_max), (5, 100.0, false));
+    }
+}
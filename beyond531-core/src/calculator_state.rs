@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::assistance::AssistancePlan;
+use crate::beyond_531::{
+    Lift, LiftId, LiftMax, MaxBasis, OneRepMax, ProgramSettings, Progression, TrainingMax, Unit,
+};
+use crate::block::BlockStructure;
+use crate::calendar::CalendarSettings;
+use crate::estimated_max::E1rmFormula;
+use crate::joker::JokerSettings;
+use crate::plate_calculator::PlateInventory;
+use crate::program_template::ProgramTemplate;
+use crate::rounding::{Rounding, RoundingMode, RoundingPolicy};
+use crate::rpe::Autoregulation;
+use crate::schedule::Schedule;
+use crate::supplemental::SupplementalPlan;
+use crate::validation::{self, WeightLimits};
+use crate::warmup::{WarmupRamp, WarmupSettings};
+
+/// One row of the lift list: what the lift is, its entered 1RM and how much it goes up per cycle.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LiftEntry {
+    pub lift: Lift,
+    pub one_rep_max: f64,
+    pub increment: f64,
+}
+
+impl LiftEntry {
+    pub fn new(lift: Lift, unit: Unit) -> Self {
+        Self {
+            increment: lift.category.default_increment(unit),
+            lift,
+            one_rep_max: 0.0,
+        }
+    }
+}
+
+/// Everything entered into the calculator, kept as plain data so it can be saved and restored.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CalculatorState {
+    pub lifts: Vec<LiftEntry>,
+    pub unit: Unit,
+    pub program_generated: bool,
+    pub use_training_max: bool,
+    pub training_max_percent: f64,
+    pub rounding_increment: f64,
+    pub rounding_mode: RoundingMode,
+    pub lift_rounding_modes: HashMap<LiftId, RoundingMode>,
+    pub plate_inventory: PlateInventory,
+    pub cycles: u32,
+    pub block: BlockStructure,
+    pub e1rm_formula: E1rmFormula,
+    pub jokers: JokerSettings,
+    pub supplemental: SupplementalPlan,
+    pub warmup_ramp: Option<WarmupRamp>,
+    pub autoregulation: Option<Autoregulation>,
+    pub assistance: AssistancePlan,
+    pub schedule: Schedule,
+    pub calendar: CalendarSettings,
+    /// Shown on exported PDFs.
+    pub athlete_name: String,
+}
+
+impl Default for CalculatorState {
+    fn default() -> Self {
+        Self::for_unit(Unit::default())
+    }
+}
+
+impl CalculatorState {
+    pub fn for_unit(unit: Unit) -> Self {
+        Self {
+            lifts: Lift::defaults()
+                .into_iter()
+                .map(|lift| LiftEntry::new(lift, unit))
+                .collect(),
+            unit,
+            program_generated: false,
+            use_training_max: false,
+            training_max_percent: TrainingMax::default().factor * 100.0,
+            rounding_increment: unit.rounding_increment(),
+            rounding_mode: RoundingMode::default(),
+            lift_rounding_modes: HashMap::new(),
+            plate_inventory: PlateInventory::standard(unit),
+            cycles: 1,
+            block: BlockStructure::default(),
+            e1rm_formula: E1rmFormula::default(),
+            jokers: JokerSettings::default(),
+            supplemental: SupplementalPlan::default(),
+            warmup_ramp: None,
+            autoregulation: None,
+            assistance: AssistancePlan::default(),
+            schedule: Schedule::default(),
+            calendar: CalendarSettings::default(),
+            athlete_name: String::new(),
+        }
+    }
+
+    pub fn one_rep_max(&self) -> OneRepMax {
+        OneRepMax {
+            lifts: self
+                .lifts
+                .iter()
+                .map(|entry| LiftMax {
+                    lift: entry.lift.clone(),
+                    weight: entry.one_rep_max,
+                })
+                .collect(),
+            unit: self.unit,
+        }
+    }
+
+    pub fn has_valid_inputs(&self) -> bool {
+        self.generate_blocker().is_none()
+    }
+
+    /// Why a program can't be generated from these inputs yet, if anything stops it.
+    pub fn generate_blocker(&self) -> Option<String> {
+        if self.lifts.is_empty() {
+            return Some("Add at least one lift".to_string());
+        }
+        if self.lifts.iter().any(|entry| entry.one_rep_max == 0.0) {
+            return Some("Enter a 1RM for every lift".to_string());
+        }
+        if let Err(err) = validation::check_rounding_increment(self.rounding_increment, self.unit) {
+            return Some(format!("Rounding increment: {}", err));
+        }
+        if self.block.needs_training_max() && !self.use_training_max {
+            return Some(format!("{} needs a Training Max", self.block.name()));
+        }
+        if self.use_training_max {
+            if let Err(err) = validation::check_training_max_percent(self.training_max_percent) {
+                return Some(format!("Training Max: {}", err));
+            }
+        }
+        self.one_rep_max().validate().first().map(ToString::to_string)
+    }
+
+    pub fn lift(&self, id: LiftId) -> Option<&LiftEntry> {
+        self.lifts.iter().find(|entry| entry.lift.id == id)
+    }
+
+    pub fn lift_mut(&mut self, id: LiftId) -> Option<&mut LiftEntry> {
+        self.lifts.iter_mut().find(|entry| entry.lift.id == id)
+    }
+
+    /// Appends a blank lift with a fresh id and returns that id.
+    pub fn add_lift(&mut self) -> LiftId {
+        let id = self.lifts.iter().map(|entry| entry.lift.id.0).max().unwrap_or(0) + 1;
+        let lift = Lift::new(id, &format!("Lift {}", self.lifts.len() + 1), Default::default());
+        self.lifts.push(LiftEntry::new(lift, self.unit));
+        LiftId(id)
+    }
+
+    pub fn remove_lift(&mut self, id: LiftId) {
+        self.lifts.retain(|entry| entry.lift.id != id);
+        self.lift_rounding_modes.remove(&id);
+        self.supplemental.templates.remove(&id);
+        self.schedule.lift_days.remove(&id);
+    }
+
+    pub fn program_settings(&self) -> ProgramSettings {
+        let basis = if self.use_training_max {
+            MaxBasis::TrainingMax(TrainingMax {
+                factor: self.training_max_percent / 100.0,
+            })
+        } else {
+            MaxBasis::OneRepMax
+        };
+        let default_rounding = Rounding {
+            increment: self.rounding_increment,
+            mode: self.rounding_mode,
+        };
+        let overrides = self
+            .lift_rounding_modes
+            .iter()
+            .map(|(&lift, &mode)| (lift, Rounding { mode, ..default_rounding }))
+            .collect();
+
+        let lifts: Vec<Lift> = self.lifts.iter().map(|entry| entry.lift.clone()).collect();
+        let mut template = ProgramTemplate::beyond_531(&lifts, &self.schedule);
+        self.block.apply_to(&mut template, &lifts, &self.schedule);
+        self.jokers.apply_to(&mut template);
+        self.supplemental.apply_to(&mut template, &self.schedule);
+
+        ProgramSettings {
+            template,
+            basis,
+            rounding: RoundingPolicy {
+                default: default_rounding,
+                overrides,
+            },
+            progression: Progression {
+                cycles: self.cycles.max(1),
+                increments: self
+                    .lifts
+                    .iter()
+                    .map(|entry| (entry.lift.id, entry.increment))
+                    .collect(),
+            },
+            e1rm_formula: self.e1rm_formula,
+            warmups: self.warmup_ramp.map(|ramp| WarmupSettings {
+                ramp,
+                bar_weight: self.plate_inventory.bar_weight,
+            }),
+            assistance: self.assistance.clone(),
+            start_date: self.calendar.start_date.unwrap_or_default(),
+            autoregulation: self.autoregulation,
+        }
+    }
+
+    /// Converts the entered maxes and swaps unit-specific settings for the new unit's defaults.
+    pub fn change_unit(&mut self, unit: Unit) {
+        if unit == self.unit {
+            return;
+        }
+        let converted = self.one_rep_max().converted_to(unit);
+        let limits = WeightLimits::for_unit(unit);
+        // Keep one decimal so a round trip doesn't drift the entered weights
+        let tidy = |weight: f64| (weight * 10.0).round() / 10.0;
+
+        for (entry, max) in self.lifts.iter_mut().zip(converted.lifts) {
+            entry.one_rep_max = limits.tidy(max.weight);
+            entry.increment = entry.lift.category.default_increment(unit);
+        }
+        for pick in &mut self.assistance.picks {
+            pick.weight = pick.weight.map(|weight| tidy(self.unit.convert(weight, unit)));
+        }
+        self.rounding_increment = unit.rounding_increment();
+        self.plate_inventory = PlateInventory::standard(unit);
+        self.unit = unit;
+    }
+}
@@ -0,0 +1,186 @@
+use chrono::{Days, NaiveDate, NaiveDateTime, NaiveTime, TimeDelta};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::{Session, TrainingProgram};
+
+/// When the program starts and how its sessions show up in a calendar.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CalendarSettings {
+    /// Filled in with today's date the first time the calculator opens.
+    pub start_date: Option<NaiveDate>,
+    /// Exported events start at this local time, or are all-day events when `None`.
+    pub session_time: Option<NaiveTime>,
+    pub session_minutes: u32,
+}
+
+impl Default for CalendarSettings {
+    fn default() -> Self {
+        Self {
+            start_date: None,
+            session_time: None,
+            session_minutes: 60,
+        }
+    }
+}
+
+/// Renders every session with work in it as an iCalendar (RFC 5545) event whose description
+/// lists the session's sets. `stamp` is the UTC time the file is created at.
+pub fn to_ics(program: &TrainingProgram, settings: &CalendarSettings, stamp: NaiveDateTime) -> String {
+    let mut lines = vec![
+        "BEGIN:VCALENDAR".to_string(),
+        "VERSION:2.0".to_string(),
+        "PRODID:-//Beyond 531//Program Export//EN".to_string(),
+        "CALSCALE:GREGORIAN".to_string(),
+    ];
+
+    for cycle in &program.cycles {
+        for week in &cycle.weeks {
+            for (session_index, session) in week.sessions.iter().enumerate() {
+                if session.exercises.is_empty() && session.assistance.is_empty() {
+                    continue;
+                }
+                let summary = if program.cycles.len() > 1 {
+                    format!("Beyond 531 - Cycle {}, Week {} ({})", cycle.cycle_number, week.week_number, session.day)
+                } else {
+                    format!("Beyond 531 - Week {} ({})", week.week_number, session.day)
+                };
+
+                lines.push("BEGIN:VEVENT".to_string());
+                lines.push(format!(
+                    "UID:{}-c{}w{}s{}@beyond531",
+                    session.date.format("%Y%m%d"),
+                    cycle.cycle_number,
+                    week.week_number,
+                    session_index
+                ));
+                lines.push(format!("DTSTAMP:{}", stamp.format("%Y%m%dT%H%M%SZ")));
+                lines.extend(event_times(session.date, settings));
+                lines.push(format!("SUMMARY:{}", escape_text(&summary)));
+                lines.push(format!("DESCRIPTION:{}", escape_text(&description(session, program))));
+                lines.push("END:VEVENT".to_string());
+            }
+        }
+    }
+    lines.push("END:VCALENDAR".to_string());
+
+    lines.iter().map(|line| fold_line(line) + "\r\n").collect()
+}
+
+fn event_times(date: NaiveDate, settings: &CalendarSettings) -> [String; 2] {
+    match settings.session_time {
+        Some(time) => {
+            // Floating times, so the event stays at the chosen hour in whatever zone the calendar is in
+            let start = date.and_time(time);
+            let end = start + TimeDelta::minutes(i64::from(settings.session_minutes.max(1)));
+            [
+                format!("DTSTART:{}", start.format("%Y%m%dT%H%M%S")),
+                format!("DTEND:{}", end.format("%Y%m%dT%H%M%S")),
+            ]
+        }
+        None => [
+            format!("DTSTART;VALUE=DATE:{}", date.format("%Y%m%d")),
+            format!("DTEND;VALUE=DATE:{}", (date + Days::new(1)).format("%Y%m%d")),
+        ],
+    }
+}
+
+fn description(session: &Session, program: &TrainingProgram) -> String {
+    session
+        .exercises
+        .iter()
+        .map(|exercise| exercise.describe(program.unit))
+        .chain(session.assistance.iter().map(|work| work.describe(program.unit)))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            ';' => escaped.push_str("\\;"),
+            ',' => escaped.push_str("\\,"),
+            '\n' => escaped.push_str("\\n"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Splits a content line into 75-octet pieces, continuing each with a leading space.
+fn fold_line(line: &str) -> String {
+    const LIMIT: usize = 75;
+    let mut folded = String::with_capacity(line.len());
+    let mut width = 0;
+
+    for c in line.chars() {
+        // Continuation lines lose one octet to the leading space
+        if width + c.len_utf8() > LIMIT {
+            folded.push_str("\r\n ");
+            width = 1;
+        }
+        folded.push(c);
+        width += c.len_utf8();
+    }
+    folded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::ProgramSettings;
+    use crate::test_support;
+
+    fn program() -> TrainingProgram {
+        test_support::program(&ProgramSettings {
+            start_date: NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(),
+            ..Default::default()
+        })
+    }
+
+    fn unfold(ics: &str) -> String {
+        ics.replace("\r\n ", "")
+    }
+
+    #[test]
+    fn exports_an_all_day_event_per_session() {
+        let stamp = NaiveDate::from_ymd_opt(2026, 10, 18).unwrap().and_hms_opt(9, 0, 0).unwrap();
+
+        let ics = to_ics(&program(), &CalendarSettings::default(), stamp);
+
+        assert!(ics.starts_with("BEGIN:VCALENDAR\r\n"));
+        assert!(ics.ends_with("END:VCALENDAR\r\n"));
+        assert_eq!(ics.matches("BEGIN:VEVENT").count(), 8);
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261019\r\nDTEND;VALUE=DATE:20261020\r\n"));
+        assert!(ics.contains("DTSTART;VALUE=DATE:20261113\r\n"));
+        assert!(ics.contains("DTSTAMP:20261018T090000Z\r\n"));
+        assert!(unfold(&ics).contains("SUMMARY:Beyond 531 - Week 1 (Monday)\r\nDESCRIPTION:Front Squat: 3x5 @ 65kg (65%)\\nDeadlift: 1x5 @ 65kg (65%)"));
+    }
+
+    #[test]
+    fn timed_events_use_the_session_length() {
+        let settings = CalendarSettings {
+            session_time: NaiveTime::from_hms_opt(18, 30, 0),
+            session_minutes: 90,
+            ..Default::default()
+        };
+
+        let ics = to_ics(&program(), &settings, NaiveDateTime::default());
+
+        assert!(ics.contains("DTSTART:20261023T183000\r\nDTEND:20261023T200000\r\n"));
+    }
+
+    #[test]
+    fn escapes_text_and_folds_long_lines() {
+        assert_eq!(escape_text("a,b;c\\d\ne"), "a\\,b\\;c\\\\d\\ne");
+
+        let folded = fold_line(&"x".repeat(160));
+        let lines: Vec<&str> = folded.split("\r\n").collect();
+        assert_eq!(lines.iter().map(|line| line.len()).collect::<Vec<_>>(), vec![75, 75, 12]);
+        assert!(lines[1].starts_with(' '));
+    }
+}
@@ -0,0 +1,197 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::{LiftId, Session, SetKind, Week};
+use crate::rpe;
+
+/// Rep-max formulas for turning an AMRAP result into an estimated 1RM.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum E1rmFormula {
+    #[default]
+    Epley,
+    Brzycki,
+    Wathan,
+}
+
+impl E1rmFormula {
+    pub const ALL: [E1rmFormula; 3] = [E1rmFormula::Epley, E1rmFormula::Brzycki, E1rmFormula::Wathan];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            E1rmFormula::Epley => "Epley",
+            E1rmFormula::Brzycki => "Brzycki",
+            E1rmFormula::Wathan => "Wathan",
+        }
+    }
+
+    /// A single is taken at face value; zero reps, or more than Brzycki can handle, give `None`.
+    pub fn estimate(&self, weight: f64, reps: u32) -> Option<f64> {
+        let r = reps as f64;
+        match (self, reps) {
+            (_, 0) => None,
+            (_, 1) => Some(weight),
+            (E1rmFormula::Epley, _) => Some(weight * (1.0 + r / 30.0)),
+            (E1rmFormula::Brzycki, _) if reps < 37 => Some(weight * 36.0 / (37.0 - r)),
+            (E1rmFormula::Brzycki, _) => None,
+            (E1rmFormula::Wathan, _) => Some(100.0 * weight / (48.8 + 53.8 * (-0.075 * r).exp())),
+        }
+    }
+}
+
+/// Identifies one prescribed set by its position in a generated program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SetKey {
+    pub cycle_number: u32,
+    pub week_number: u32,
+    pub session_index: usize,
+    /// Position among the session's main sets only.
+    pub exercise_index: usize,
+}
+
+impl SetKey {
+    /// A key per exercise in `session`, or `None` for anything but a main set, so adding
+    /// or removing extra sets never moves a logged result.
+    pub fn for_session(cycle_number: u32, week_number: u32, session_index: usize, session: &Session) -> Vec<Option<SetKey>> {
+        let mut main_sets = 0;
+        session
+            .exercises
+            .iter()
+            .map(|exercise| {
+                (exercise.kind == SetKind::Main).then(|| {
+                    main_sets += 1;
+                    SetKey {
+                        cycle_number,
+                        week_number,
+                        session_index,
+                        exercise_index: main_sets - 1,
+                    }
+                })
+            })
+            .collect()
+    }
+}
+
+/// Reps actually completed on AMRAP sets, and how hard RPE-prescribed sets felt.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TrainingLog {
+    pub amrap_reps: HashMap<SetKey, u32>,
+    pub rpe: HashMap<SetKey, f64>,
+}
+
+impl TrainingLog {
+    pub fn reps_for(&self, key: &SetKey) -> Option<u32> {
+        self.amrap_reps.get(key).copied()
+    }
+
+    pub fn record(&mut self, key: SetKey, reps: Option<u32>) {
+        match reps {
+            Some(reps) => self.amrap_reps.insert(key, reps),
+            None => self.amrap_reps.remove(&key),
+        };
+    }
+
+    pub fn rpe_for(&self, key: &SetKey) -> Option<f64> {
+        self.rpe.get(key).copied()
+    }
+
+    pub fn record_rpe(&mut self, key: SetKey, rpe: Option<f64>) {
+        match rpe {
+            Some(rpe) => self.rpe.insert(key, rpe),
+            None => self.rpe.remove(&key),
+        };
+    }
+
+    /// Best estimated 1RM per lift over the AMRAP and RPE sets logged in `weeks` of a cycle.
+    pub fn best_estimates<'a>(
+        &self,
+        cycle_number: u32,
+        weeks: impl IntoIterator<Item = &'a Week>,
+        formula: E1rmFormula,
+    ) -> HashMap<LiftId, f64> {
+        let mut best: HashMap<LiftId, f64> = HashMap::new();
+
+        for week in weeks {
+            for (session_index, session) in week.sessions.iter().enumerate() {
+                let keys = SetKey::for_session(cycle_number, week.week_number, session_index, session);
+                for (exercise, key) in session.exercises.iter().zip(keys) {
+                    let Some(key) = key else {
+                        continue;
+                    };
+                    let estimate = if exercise.is_amrap {
+                        self.reps_for(&key).and_then(|reps| formula.estimate(exercise.weight, reps))
+                    } else {
+                        self.rpe_for(&key).and_then(|rpe| rpe::estimate_max(exercise.weight, exercise.reps, rpe))
+                    };
+                    let Some(estimate) = estimate else {
+                        continue;
+                    };
+                    let entry = best.entry(exercise.lift).or_insert(estimate);
+                    *entry = entry.max(estimate);
+                }
+            }
+        }
+
+        best
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::Exercise;
+
+    #[test]
+    fn formulas_agree_on_a_single() {
+        for formula in E1rmFormula::ALL {
+            assert_eq!(formula.estimate(140.0, 1), Some(140.0));
+        }
+    }
+
+    #[test]
+    fn formulas_match_reference_values() {
+        let round = |value: Option<f64>| value.map(|value| (value * 10.0).round() / 10.0);
+
+        assert_eq!(round(E1rmFormula::Epley.estimate(100.0, 5)), Some(116.7));
+        assert_eq!(round(E1rmFormula::Brzycki.estimate(100.0, 5)), Some(112.5));
+        assert_eq!(round(E1rmFormula::Wathan.estimate(100.0, 5)), Some(116.6));
+    }
+
+    #[test]
+    fn only_main_sets_are_keyed() {
+        let exercise = |kind| Exercise {
+            name: "Deadlift".to_string(),
+            lift: LiftId(2),
+            kind,
+            sets: 1,
+            reps: 5,
+            weight: 150.0,
+            percentage: 80.0,
+            is_amrap: false,
+            stop_rule: None,
+            rpe: None,
+        };
+        let session = Session {
+            day: "Friday".to_string(),
+            date: Default::default(),
+            exercises: vec![exercise(SetKind::Main), exercise(SetKind::Joker), exercise(SetKind::Main)],
+            assistance: Vec::new(),
+        };
+
+        let indices: Vec<Option<usize>> = SetKey::for_session(1, 1, 1, &session)
+            .iter()
+            .map(|key| key.map(|key| key.exercise_index))
+            .collect();
+
+        assert_eq!(indices, vec![Some(0), None, Some(1)]);
+    }
+
+    #[test]
+    fn zero_reps_has_no_estimate() {
+        assert_eq!(E1rmFormula::Epley.estimate(100.0, 0), None);
+        assert_eq!(E1rmFormula::Brzycki.estimate(100.0, 40), None);
+    }
+}
@@ -0,0 +1,168 @@
+use std::fmt;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::SetKind;
+use crate::program_template::{ProgramTemplate, SetPrescription};
+
+/// When to stop taking further joker jumps, recorded on each joker set.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum StopRule {
+    BarSpeed,
+    RpeCap(f64),
+}
+
+impl StopRule {
+    pub const OPTIONS: [StopRule; 3] = [StopRule::BarSpeed, StopRule::RpeCap(8.0), StopRule::RpeCap(9.0)];
+}
+
+impl fmt::Display for StopRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StopRule::BarSpeed => write!(f, "stop when bar speed slows"),
+            StopRule::RpeCap(rpe) => write!(f, "stop once a set goes past RPE {}", rpe),
+        }
+    }
+}
+
+/// Heavier sets after a lift's top set: jokers follow AMRAP top sets, and
+/// "beyond" extensions keep climbing after the max week's last single.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct JokerSettings {
+    pub jokers: bool,
+    pub beyond_extensions: bool,
+    /// The most extra sets to offer after each top set.
+    pub sets: u32,
+    /// Jump per extra set in percentage points, between 5 and 10.
+    pub jump_percent: f64,
+    pub stop_rule: StopRule,
+}
+
+impl Default for JokerSettings {
+    fn default() -> Self {
+        Self {
+            jokers: false,
+            beyond_extensions: false,
+            sets: 3,
+            jump_percent: 5.0,
+            stop_rule: StopRule::BarSpeed,
+        }
+    }
+}
+
+impl JokerSettings {
+    pub const MIN_JUMP: f64 = 5.0;
+    pub const MAX_JUMP: f64 = 10.0;
+
+    /// Inserts the extra sets right after each lift's top set.
+    pub fn apply_to(&self, template: &mut ProgramTemplate) {
+        let lift_templates = template
+            .weeks
+            .iter_mut()
+            .flat_map(|week| &mut week.sessions)
+            .flat_map(|session| &mut session.lifts);
+
+        for lift_template in lift_templates {
+            let Some(top_index) = lift_template.sets.iter().rposition(|set| set.kind == SetKind::Main) else {
+                continue;
+            };
+            let extras = self.extra_sets(&lift_template.sets[top_index]);
+            lift_template.sets.splice(top_index + 1..top_index + 1, extras);
+        }
+    }
+
+    fn extra_sets(&self, top_set: &SetPrescription) -> Vec<SetPrescription> {
+        // Jokers are triples or singles depending on the top set; extensions are always singles
+        let (reps, label) = if top_set.is_amrap && self.jokers {
+            (top_set.reps.min(3), "Joker")
+        } else if !top_set.is_amrap && top_set.of_one_rep_max && self.beyond_extensions {
+            (1, "Beyond")
+        } else {
+            return Vec::new();
+        };
+        let jump = self.jump_percent.clamp(Self::MIN_JUMP, Self::MAX_JUMP);
+
+        (1..=self.sets)
+            .map(|set| {
+                let mut extra = SetPrescription::new(1, reps, top_set.percentage + jump * set as f64)
+                    .joker()
+                    .labelled(label)
+                    .until(self.stop_rule);
+                extra.of_one_rep_max = top_set.of_one_rep_max;
+                extra
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn friday_sets(template: &ProgramTemplate, week_number: u32) -> Vec<SetPrescription> {
+        template
+            .weeks
+            .iter()
+            .filter(|week| week.week_number == week_number)
+            .flat_map(|week| &week.sessions)
+            .filter(|session| session.day == "Friday")
+            .flat_map(|session| session.lifts.first())
+            .flat_map(|lift_template| lift_template.sets.clone())
+            .collect()
+    }
+
+    #[test]
+    fn jokers_follow_the_amrap_top_set() {
+        let mut template = ProgramTemplate::default();
+        let settings = JokerSettings {
+            jokers: true,
+            sets: 2,
+            jump_percent: 10.0,
+            ..Default::default()
+        };
+
+        settings.apply_to(&mut template);
+
+        let sets = friday_sets(&template, 2);
+        let jokers: Vec<(u32, f64)> = sets[3..].iter().map(|set| (set.reps, set.percentage)).collect();
+        assert!(sets[2].is_amrap);
+        assert_eq!(jokers, vec![(3, 95.0), (3, 105.0)]);
+        assert!(sets[3..].iter().all(|set| set.kind == SetKind::Joker && set.stop_rule == Some(StopRule::BarSpeed)));
+        assert_eq!(friday_sets(&template, 4).len(), 5);
+    }
+
+    #[test]
+    fn beyond_extensions_climb_past_the_max_week_single() {
+        let mut template = ProgramTemplate::default();
+        let settings = JokerSettings {
+            beyond_extensions: true,
+            stop_rule: StopRule::RpeCap(9.0),
+            ..Default::default()
+        };
+
+        settings.apply_to(&mut template);
+
+        let extensions: Vec<f64> = friday_sets(&template, 4)[5..].iter().map(|set| set.percentage).collect();
+        assert_eq!(extensions, vec![110.0, 115.0, 120.0]);
+        assert_eq!(friday_sets(&template, 1).len(), 3);
+    }
+
+    #[test]
+    fn jumps_are_kept_between_five_and_ten_percent() {
+        let mut template = ProgramTemplate::default();
+        let settings = JokerSettings {
+            jokers: true,
+            sets: 1,
+            jump_percent: 25.0,
+            ..Default::default()
+        };
+
+        settings.apply_to(&mut template);
+
+        assert_eq!(friday_sets(&template, 3)[3].percentage, 100.0);
+    }
+}
@@ -0,0 +1,27 @@
+//! The Beyond 531 calculator: lifts, templates and program generation, with no browser
+//! dependencies. Enable the `serde` feature to save, export and import programs as JSON.
+
+pub mod assistance;
+pub mod beyond_531;
+pub mod block;
+pub mod calculator_state;
+pub mod calendar;
+pub mod estimated_max;
+pub mod joker;
+pub mod pdf;
+pub mod plate_calculator;
+#[cfg(feature = "serde")]
+pub mod program_file;
+pub mod program_template;
+pub mod rounding;
+pub mod rpe;
+#[cfg(feature = "serde")]
+pub mod saved_state;
+pub mod schedule;
+pub mod share_link;
+pub mod supplemental;
+#[cfg(any(test, feature = "test-support"))]
+#[doc(hidden)]
+pub mod test_support;
+pub mod validation;
+pub mod warmup;
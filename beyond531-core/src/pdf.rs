@@ -0,0 +1,401 @@
+use std::fmt::Write;
+
+use crate::beyond_531::{Cycle, TrainingProgram, Unit, Week};
+
+// A4 portrait, in points
+const PAGE_WIDTH: f64 = 595.0;
+const PAGE_HEIGHT: f64 = 842.0;
+const MARGIN: f64 = 40.0;
+const ROW_HEIGHT: f64 = 14.0;
+const NOTE_LINES: usize = 8;
+
+/// Table columns as (heading, left edge, width).
+const COLUMNS: [(&str, f64, f64); 6] = [
+    ("Day", MARGIN, 75.0),
+    ("Exercise", MARGIN + 75.0, 215.0),
+    ("Sets x Reps", MARGIN + 290.0, 65.0),
+    ("Weight", MARGIN + 355.0, 60.0),
+    ("%", MARGIN + 415.0, 35.0),
+    ("Done", MARGIN + 450.0, 65.0),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource(&self) -> &'static str {
+        match self {
+            Font::Regular => "F1",
+            Font::Bold => "F2",
+        }
+    }
+}
+
+/// Lays content out top to bottom, starting a new page whenever the next block doesn't fit.
+struct Layout {
+    pages: Vec<String>,
+    y: f64,
+    continued_title: String,
+}
+
+impl Layout {
+    fn new(continued_title: String) -> Self {
+        Self {
+            pages: vec![String::new()],
+            y: PAGE_HEIGHT - MARGIN,
+            continued_title,
+        }
+    }
+
+    fn page(&mut self) -> &mut String {
+        self.pages.last_mut().expect("layout always has a page")
+    }
+
+    fn ensure_space(&mut self, height: f64) {
+        if self.y - height >= MARGIN + ROW_HEIGHT {
+            return;
+        }
+        self.pages.push(String::new());
+        self.y = PAGE_HEIGHT - MARGIN;
+        let title = self.continued_title.clone();
+        self.text(MARGIN, self.y - 10.0, 9.0, Font::Regular, &title);
+        self.y -= 24.0;
+    }
+
+    fn text(&mut self, x: f64, y: f64, size: f64, font: Font, text: &str) {
+        let encoded = encode_text(text);
+        let _ = writeln!(self.page(), "BT /{} {} Tf {:.1} {:.1} Td ({}) Tj ET", font.resource(), size, x, y, encoded);
+    }
+
+    /// Text cut short with "..." so it stays within `width`.
+    fn text_within(&mut self, x: f64, y: f64, width: f64, font: Font, text: &str) {
+        let size = 9.0;
+        let fitted = if text_width(text, size) <= width {
+            text.to_string()
+        } else {
+            let mut fitted = String::new();
+            for c in text.chars() {
+                if text_width(&format!("{}{}...", fitted, c), size) > width {
+                    break;
+                }
+                fitted.push(c);
+            }
+            fitted + "..."
+        };
+        self.text(x, y, size, font, &fitted);
+    }
+
+    fn line(&mut self, x1: f64, y1: f64, x2: f64, y2: f64) {
+        let _ = writeln!(self.page(), "{:.1} {:.1} m {:.1} {:.1} l S", x1, y1, x2, y2);
+    }
+
+    fn square(&mut self, x: f64, y: f64, size: f64) {
+        let _ = writeln!(self.page(), "{:.1} {:.1} {:.1} {:.1} re S", x, y, size, size);
+    }
+
+    fn shaded_row(&mut self, y: f64) {
+        let width = PAGE_WIDTH - 2.0 * MARGIN;
+        let _ = writeln!(self.page(), "0.9 g {:.1} {:.1} {:.1} {:.1} re f 0 g", MARGIN, y, width, ROW_HEIGHT);
+    }
+}
+
+/// Renders `program` as a PDF: a header with the athlete, maxes and unit, a table per
+/// week and a notes area at the end.
+pub fn program_pdf(program: &TrainingProgram, athlete: &str) -> Vec<u8> {
+    let athlete = if athlete.trim().is_empty() { "Athlete" } else { athlete.trim() };
+    let mut layout = Layout::new(format!("Beyond 531 program - {} (continued)", athlete));
+
+    write_header(&mut layout, program, athlete);
+    for cycle in &program.cycles {
+        if cycle.cycle_number > 1 {
+            layout.ensure_space(ROW_HEIGHT * 6.0);
+            layout.y -= ROW_HEIGHT;
+            layout.text(MARGIN, layout.y, 11.0, Font::Bold, &format!("Cycle {} 1RMs: {}", cycle.cycle_number, maxes(cycle, program.unit)));
+            layout.y -= 6.0;
+        }
+        for week in &cycle.weeks {
+            let title = if program.cycles.len() > 1 {
+                format!("Cycle {} - {}", cycle.cycle_number, week.title())
+            } else {
+                week.title()
+            };
+            write_week_table(&mut layout, &title, &week_rows(week, program.unit));
+        }
+    }
+    write_notes(&mut layout);
+
+    let page_count = layout.pages.len();
+    for (index, page) in layout.pages.iter_mut().enumerate() {
+        let footer = encode_text(&format!("Page {} of {}", index + 1, page_count));
+        let _ = writeln!(page, "BT /F1 8 Tf {:.1} {:.1} Td ({}) Tj ET", PAGE_WIDTH - MARGIN - 45.0, MARGIN - 20.0, footer);
+    }
+
+    assemble(&layout.pages)
+}
+
+fn maxes(cycle: &Cycle, unit: Unit) -> String {
+    cycle
+        .one_rep_max
+        .lifts
+        .iter()
+        .map(|max| format!("{} {}{}", max.lift.name, max.weight, unit))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn write_header(layout: &mut Layout, program: &TrainingProgram, athlete: &str) {
+    // The starting maxes; each later cycle lists its own above its first week
+    let maxes = program.cycles.first().map(|cycle| maxes(cycle, program.unit)).unwrap_or_default();
+
+    layout.text(MARGIN, layout.y - 18.0, 18.0, Font::Bold, "Beyond 531 Program");
+    layout.y -= 36.0;
+    layout.text(MARGIN, layout.y, 11.0, Font::Regular, &format!("Athlete: {}", athlete));
+    layout.y -= 15.0;
+    layout.text(MARGIN, layout.y, 11.0, Font::Regular, &format!("1RMs: {}", maxes));
+    layout.y -= 15.0;
+    layout.text(MARGIN, layout.y, 11.0, Font::Regular, &format!("Unit: {}", program.unit.name()));
+    layout.y -= 10.0;
+    layout.line(MARGIN, layout.y, PAGE_WIDTH - MARGIN, layout.y);
+    layout.y -= 10.0;
+}
+
+/// One table line: day, exercise, sets x reps, weight and percentage (or target RPE), plus
+/// the label of a blank for AMRAP reps or the RPE reached, shown in the "Done" column
+/// instead of a tick box.
+struct TableRow {
+    cells: [String; 5],
+    blank: Option<&'static str>,
+}
+
+fn week_rows(week: &Week, unit: Unit) -> Vec<TableRow> {
+    let mut rows = Vec::new();
+
+    for session in &week.sessions {
+        let first_row = rows.len();
+        for exercise in &session.exercises {
+            let reps = if exercise.is_amrap { format!("{}+", exercise.reps) } else { exercise.reps.to_string() };
+            rows.push(TableRow {
+                cells: [
+                    String::new(),
+                    exercise.name.clone(),
+                    format!("{}x{}", exercise.sets, reps),
+                    format!("{}{}", exercise.weight, unit),
+                    exercise.intensity(),
+                ],
+                blank: if exercise.is_amrap {
+                    Some("reps:")
+                } else {
+                    exercise.rpe.map(|_| "RPE:")
+                },
+            });
+        }
+        for work in &session.assistance {
+            rows.push(TableRow {
+                cells: [
+                    String::new(),
+                    work.name.clone(),
+                    format!("{}x{}", work.sets, work.reps),
+                    work.weight.map(|weight| format!("{}{}", weight, unit)).unwrap_or_default(),
+                    String::new(),
+                ],
+                blank: None,
+            });
+        }
+        if let Some(row) = rows.get_mut(first_row) {
+            row.cells[0] = session.date.format("%a %-d %b").to_string();
+        }
+    }
+
+    rows
+}
+
+fn write_week_table(layout: &mut Layout, title: &str, rows: &[TableRow]) {
+    // Keep the title with the heading row and at least the first few rows
+    layout.ensure_space(24.0 + ROW_HEIGHT * (rows.len().min(4) + 1) as f64);
+    layout.text(MARGIN, layout.y - 14.0, 13.0, Font::Bold, title);
+    layout.y -= 22.0;
+    write_table_heading(layout);
+
+    for row in rows {
+        if layout.y - ROW_HEIGHT < MARGIN + ROW_HEIGHT {
+            layout.ensure_space(ROW_HEIGHT * 2.0);
+            write_table_heading(layout);
+        }
+        layout.y -= ROW_HEIGHT;
+        let baseline = layout.y + 4.0;
+        for (cell, (_, x, width)) in row.cells.iter().zip(&COLUMNS) {
+            layout.text_within(x + 2.0, baseline, width - 4.0, Font::Regular, cell);
+        }
+        let (_, done_x, _) = COLUMNS[5];
+        if let Some(label) = row.blank {
+            layout.text(done_x + 2.0, baseline, 9.0, Font::Regular, label);
+            layout.line(done_x + 28.0, baseline - 1.0, done_x + 60.0, baseline - 1.0);
+        } else {
+            layout.square(done_x + 4.0, baseline - 1.0, 8.0);
+        }
+        layout.line(MARGIN, layout.y, PAGE_WIDTH - MARGIN, layout.y);
+    }
+    layout.y -= 12.0;
+}
+
+fn write_table_heading(layout: &mut Layout) {
+    layout.y -= ROW_HEIGHT;
+    layout.shaded_row(layout.y);
+    for (heading, x, width) in COLUMNS {
+        layout.text_within(x + 2.0, layout.y + 4.0, width - 4.0, Font::Bold, heading);
+    }
+}
+
+fn write_notes(layout: &mut Layout) {
+    layout.ensure_space(24.0 + ROW_HEIGHT * 1.5 * NOTE_LINES as f64);
+    layout.text(MARGIN, layout.y - 14.0, 13.0, Font::Bold, "Notes");
+    layout.y -= 18.0;
+    for _ in 0..NOTE_LINES {
+        layout.y -= ROW_HEIGHT * 1.5;
+        layout.line(MARGIN, layout.y, PAGE_WIDTH - MARGIN, layout.y);
+    }
+}
+
+/// Writes the objects, cross-reference table and trailer around the page content streams.
+fn assemble(pages: &[String]) -> Vec<u8> {
+    // 1: catalog, 2: page tree, 3 and 4: fonts, then a page and its content stream per page
+    let page_id = |index: usize| 5 + 2 * index;
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len()).map(|index| format!("{} 0 R", page_id(index))).collect::<Vec<_>>().join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>".to_string(),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>".to_string(),
+    ];
+    for (index, content) in pages.iter().enumerate() {
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            page_id(index) + 1
+        ));
+        objects.push(format!("<< /Length {} >>\nstream\n{}endstream", content.len(), content));
+    }
+
+    let mut pdf = String::from("%PDF-1.4\n");
+    let mut offsets = Vec::with_capacity(objects.len());
+    for (index, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        let _ = write!(pdf, "{} 0 obj\n{}\nendobj\n", index + 1, object);
+    }
+    let xref_offset = pdf.len();
+    let _ = write!(pdf, "xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+    for offset in offsets {
+        let _ = writeln!(pdf, "{:010} 00000 n ", offset);
+    }
+    let _ = write!(
+        pdf,
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref_offset
+    );
+
+    pdf.into_bytes()
+}
+
+/// A PDF string literal body in WinAnsi encoding; anything outside it becomes "?".
+/// Non-ASCII bytes are written as octal escapes so the file stays ASCII.
+fn encode_text(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '\\' | '(' | ')' => {
+                encoded.push('\\');
+                encoded.push(c);
+            }
+            ' '..='~' => encoded.push(c),
+            _ => {
+                let byte = match c {
+                    '–' => 0x96,
+                    '—' => 0x97,
+                    '•' => 0x95,
+                    '…' => 0x85,
+                    '\u{a0}'..='\u{ff}' => c as u32,
+                    _ => '?' as u32,
+                };
+                let _ = write!(encoded, "\\{:03o}", byte);
+            }
+        }
+    }
+    encoded
+}
+
+/// Width of `text` in points, from Helvetica's metrics; characters outside ASCII count as an "n".
+fn text_width(text: &str, size: f64) -> f64 {
+    const WIDTHS: [u16; 95] = [
+        278, 278, 355, 556, 556, 889, 667, 191, 333, 333, 389, 584, 278, 333, 278, 278, // space to /
+        556, 556, 556, 556, 556, 556, 556, 556, 556, 556, 278, 278, 584, 584, 584, 556, // 0 to ?
+        1015, 667, 667, 722, 722, 667, 611, 778, 722, 278, 500, 667, 556, 833, 722, 778, // @ to O
+        667, 778, 722, 667, 611, 722, 667, 944, 667, 667, 611, 278, 278, 278, 469, 556, // P to _
+        333, 556, 556, 500, 556, 556, 278, 556, 556, 222, 222, 500, 222, 833, 556, 556, // ` to o
+        556, 556, 333, 500, 278, 556, 500, 722, 500, 500, 500, 334, 260, 334, 584, // p to ~
+    ];
+    let units: u32 = text
+        .chars()
+        .map(|c| match c {
+            ' '..='~' => u32::from(WIDTHS[c as usize - 32]),
+            _ => 556,
+        })
+        .sum();
+    f64::from(units) * size / 1000.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::{ProgramSettings, Progression};
+    use crate::test_support;
+
+    fn program(cycles: u32) -> TrainingProgram {
+        test_support::program(&ProgramSettings {
+            progression: Progression {
+                cycles,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn cross_reference_offsets_point_at_their_objects() {
+        let pdf = String::from_utf8(program_pdf(&program(1), "Sam")).unwrap();
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        let xref = &pdf[pdf.find("xref\n").unwrap()..];
+        for (index, line) in xref.lines().skip(3).take_while(|line| line.ends_with(" n ")).enumerate() {
+            let offset: usize = line[..10].parse().unwrap();
+            assert!(pdf[offset..].starts_with(&format!("{} 0 obj", index + 1)));
+        }
+        assert!(pdf.contains("(Athlete: Sam) Tj"));
+        assert!(pdf.contains("(Notes) Tj"));
+    }
+
+    #[test]
+    fn longer_programs_flow_onto_more_pages() {
+        let one_cycle = String::from_utf8(program_pdf(&program(1), "")).unwrap();
+        let three_cycles = String::from_utf8(program_pdf(&program(3), "")).unwrap();
+
+        let page_count = |pdf: &str| pdf.matches("/Type /Page ").count();
+        assert!(page_count(&one_cycle) >= 2);
+        assert!(page_count(&three_cycles) > page_count(&one_cycle));
+        assert!(three_cycles.contains(&format!("(Page {} of {}) Tj", page_count(&three_cycles), page_count(&three_cycles))));
+    }
+
+    #[test]
+    fn escapes_and_encodes_text() {
+        assert_eq!(encode_text("Squat (paused) \\ 5×5 – é ≈"), "Squat \\(paused\\) \\\\ 5\\3275 \\226 \\351 \\077");
+        assert_eq!(text_width("ii", 10.0), 4.44);
+    }
+}
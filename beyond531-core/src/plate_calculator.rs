@@ -0,0 +1,239 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::Unit;
+
+/// How many plates of one size are available in total (both sides of the bar).
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlateStock {
+    pub weight: f64,
+    pub count: u32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PlateInventory {
+    pub bar_weight: f64,
+    pub plates: Vec<PlateStock>,
+}
+
+impl Default for PlateInventory {
+    fn default() -> Self {
+        Self::standard(Unit::default())
+    }
+}
+
+/// The plates to put on each side of the bar for a prescribed weight.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlateLoad {
+    pub target: f64,
+    /// Closest weight the inventory can build; equals `target` when exactly loadable.
+    pub achieved: f64,
+    /// Heaviest first.
+    pub per_side: Vec<f64>,
+}
+
+impl PlateLoad {
+    pub fn is_exact(&self) -> bool {
+        (self.achieved - self.target).abs() < PlateInventory::RESOLUTION / 2.0
+    }
+}
+
+impl PlateInventory {
+    /// Plate weights are matched on a grid this fine, which covers 0.25 kg and 1.25 lb plates.
+    const RESOLUTION: f64 = 0.05;
+
+    pub fn standard(unit: Unit) -> Self {
+        let (bar_weight, plates): (f64, &[(f64, u32)]) = match unit {
+            Unit::Kilograms => (
+                20.0,
+                &[
+                    (25.0, 6),
+                    (20.0, 8),
+                    (15.0, 2),
+                    (10.0, 4),
+                    (5.0, 4),
+                    (2.5, 4),
+                    (1.25, 4),
+                    (0.5, 0),
+                    (0.25, 0),
+                ],
+            ),
+            Unit::Pounds => (
+                45.0,
+                &[
+                    (45.0, 12),
+                    (35.0, 2),
+                    (25.0, 4),
+                    (10.0, 4),
+                    (5.0, 4),
+                    (2.5, 4),
+                    (1.25, 0),
+                ],
+            ),
+        };
+
+        Self {
+            bar_weight,
+            plates: plates
+                .iter()
+                .map(|&(weight, count)| PlateStock { weight, count })
+                .collect(),
+        }
+    }
+
+    /// Finds the per-side plates that get closest to `target`, using as few plates as possible
+    /// and favouring heavier plates among equally short options. Ties between a lighter and a
+    /// heavier achievable weight go to the lighter one.
+    pub fn load(&self, target: f64) -> PlateLoad {
+        // Lightest first, so backtracking from the end settles the heaviest plates first
+        let mut plates: Vec<(usize, usize)> = self
+            .plates
+            .iter()
+            .map(|stock| (Self::to_units(stock.weight.max(0.0)), (stock.count / 2) as usize))
+            .filter(|&(weight, count)| weight > 0 && count > 0)
+            .collect();
+        plates.sort_unstable();
+
+        let side_target = Self::to_units(((target - self.bar_weight) / 2.0).max(0.0));
+        let heaviest = plates.last().map(|&(weight, _)| weight).unwrap_or(0);
+        let available: usize = plates.iter().map(|&(weight, count)| weight * count).sum();
+        let limit = (side_target + heaviest).min(available);
+
+        // fewest[i][sum] = fewest plates from the first i sizes that add up to `sum`
+        let mut fewest = vec![vec![None; limit + 1]; plates.len() + 1];
+        fewest[0][0] = Some(0);
+        for (i, &(weight, count)) in plates.iter().enumerate() {
+            for sum in 0..=limit {
+                fewest[i + 1][sum] = (0..=count.min(sum / weight))
+                    .filter_map(|k| fewest[i][sum - k * weight].map(|used: usize| used + k))
+                    .min();
+            }
+        }
+
+        let best_sum = (0..=limit)
+            .filter(|&sum| fewest[plates.len()][sum].is_some())
+            .min_by_key(|&sum| (sum.abs_diff(side_target), sum))
+            .unwrap_or(0);
+
+        let mut per_side = Vec::new();
+        let mut sum = best_sum;
+        for (i, &(weight, count)) in plates.iter().enumerate().rev() {
+            let used = fewest[i + 1][sum];
+            let k = (0..=count.min(sum / weight))
+                .rev()
+                .find(|&k| fewest[i][sum - k * weight].map(|rest| rest + k) == used)
+                .unwrap_or(0);
+            per_side.extend(std::iter::repeat_n(Self::from_units(weight), k));
+            sum -= k * weight;
+        }
+
+        PlateLoad {
+            target,
+            achieved: self.bar_weight + 2.0 * Self::from_units(best_sum),
+            per_side,
+        }
+    }
+
+    fn to_units(weight: f64) -> usize {
+        (weight / Self::RESOLUTION).round() as usize
+    }
+
+    fn from_units(units: usize) -> f64 {
+        // Round through hundredths so 0.05 steps don't print as 142.50000000000003
+        ((units as f64 * Self::RESOLUTION) * 100.0).round() / 100.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inventory(bar_weight: f64, plates: &[(f64, u32)]) -> PlateInventory {
+        PlateInventory {
+            bar_weight,
+            plates: plates
+                .iter()
+                .map(|&(weight, count)| PlateStock { weight, count })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn loads_standard_weight_with_fewest_plates() {
+        let load = PlateInventory::standard(Unit::Kilograms).load(142.5);
+
+        assert!(load.is_exact());
+        assert_eq!(load.achieved, 142.5);
+        assert_eq!(load.per_side, vec![25.0, 25.0, 10.0, 1.25]);
+    }
+
+    #[test]
+    fn empty_bar_needs_no_plates() {
+        let load = PlateInventory::standard(Unit::Kilograms).load(20.0);
+
+        assert!(load.is_exact());
+        assert!(load.per_side.is_empty());
+    }
+
+    #[test]
+    fn target_below_bar_reports_bar_weight() {
+        let load = PlateInventory::standard(Unit::Pounds).load(30.0);
+
+        assert!(!load.is_exact());
+        assert_eq!(load.achieved, 45.0);
+        assert!(load.per_side.is_empty());
+    }
+
+    #[test]
+    fn uses_fractional_plates_when_available() {
+        let plates = inventory(20.0, &[(20.0, 2), (1.25, 2), (0.5, 2)]);
+
+        let load = plates.load(61.0);
+
+        assert!(load.is_exact());
+        assert_eq!(load.per_side, vec![20.0, 0.5]);
+    }
+
+    #[test]
+    fn respects_limited_quantities() {
+        // Only one pair of 20s, so 100 kg has to be built from smaller plates
+        let plates = inventory(20.0, &[(20.0, 2), (15.0, 2), (5.0, 4)]);
+
+        let load = plates.load(100.0);
+
+        assert!(load.is_exact());
+        assert_eq!(load.per_side, vec![20.0, 15.0, 5.0]);
+    }
+
+    #[test]
+    fn falls_back_to_nearest_loadable_weight() {
+        let plates = inventory(20.0, &[(20.0, 2), (5.0, 2)]);
+
+        let load = plates.load(71.0);
+
+        assert!(!load.is_exact());
+        assert_eq!(load.achieved, 70.0);
+        assert_eq!(load.per_side, vec![20.0, 5.0]);
+    }
+
+    #[test]
+    fn prefers_lighter_option_on_ties() {
+        let plates = inventory(20.0, &[(5.0, 4)]);
+
+        let load = plates.load(25.0);
+
+        assert_eq!(load.achieved, 20.0);
+    }
+
+    #[test]
+    fn caps_at_heaviest_possible_load() {
+        let plates = inventory(20.0, &[(20.0, 2)]);
+
+        let load = plates.load(200.0);
+
+        assert!(!load.is_exact());
+        assert_eq!(load.achieved, 60.0);
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+
+use serde_json::{json, Value};
+
+use crate::beyond_531::{SetKind, TrainingProgram};
+use crate::calculator_state::CalculatorState;
+use crate::estimated_max::TrainingLog;
+use crate::saved_state::{self, SavedStateError};
+
+/// Marks a JSON file as an exported program so other JSON is rejected up front.
+pub const FORMAT: &str = "beyond531-program";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgramFileError {
+    Malformed(String),
+    NotAProgram,
+    Inputs(SavedStateError),
+    Invalid(String),
+}
+
+impl fmt::Display for ProgramFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProgramFileError::Malformed(reason) => write!(f, "the file is not valid JSON: {}", reason),
+            ProgramFileError::NotAProgram => write!(f, "the file is not a Beyond 531 program export"),
+            ProgramFileError::Inputs(err) => write!(f, "the program's inputs can't be read: {}", err),
+            ProgramFileError::Invalid(reason) => write!(f, "the program is invalid: {}", reason),
+        }
+    }
+}
+
+/// A program read back from an export, along with the inputs and log it was generated from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedProgram {
+    pub state: CalculatorState,
+    pub log: TrainingLog,
+    pub program: TrainingProgram,
+}
+
+/// The generated program plus everything needed to regenerate it, as pretty-printed JSON.
+pub fn to_json(state: &CalculatorState, log: &TrainingLog, program: &TrainingProgram) -> String {
+    let file = json!({
+        "format": FORMAT,
+        "inputs": saved_state::to_value(state, log),
+        "program": program,
+    });
+
+    serde_json::to_string_pretty(&file).expect("program files always serialize")
+}
+
+pub fn from_json(json: &str) -> Result<ImportedProgram, ProgramFileError> {
+    let mut file: Value = serde_json::from_str(json).map_err(|err| ProgramFileError::Malformed(err.to_string()))?;
+    if file.get("format").and_then(Value::as_str) != Some(FORMAT) {
+        return Err(ProgramFileError::NotAProgram);
+    }
+
+    let inputs = file.get_mut("inputs").map(Value::take).ok_or_else(|| missing("inputs"))?;
+    let (state, log) = saved_state::from_value(inputs).map_err(ProgramFileError::Inputs)?;
+    let program = file.get_mut("program").map(Value::take).ok_or_else(|| missing("program"))?;
+    let program: TrainingProgram =
+        serde_json::from_value(program).map_err(|err| ProgramFileError::Invalid(err.to_string()))?;
+
+    validate(&state, &program)?;
+    Ok(ImportedProgram { state, log, program })
+}
+
+fn missing(field: &str) -> ProgramFileError {
+    ProgramFileError::Invalid(format!("missing \"{}\"", field))
+}
+
+fn validate(state: &CalculatorState, program: &TrainingProgram) -> Result<(), ProgramFileError> {
+    let invalid = |reason: String| Err(ProgramFileError::Invalid(reason));
+
+    if let Some(error) = state.one_rep_max().validate().first() {
+        return invalid(error.to_string());
+    }
+    if program.unit != state.unit {
+        return invalid(format!("the program is in {} but its inputs are in {}", program.unit.name(), state.unit.name()));
+    }
+    if program.cycles.is_empty() {
+        return invalid("it has no cycles".to_string());
+    }
+
+    for cycle in &program.cycles {
+        for week in &cycle.weeks {
+            for session in &week.sessions {
+                let at = format!("cycle {}, week {}, {}", cycle.cycle_number, week.week_number, session.day);
+                for exercise in &session.exercises {
+                    if state.lift(exercise.lift).is_none() {
+                        return invalid(format!("{} has a set for a lift that isn't in the inputs ({})", at, exercise.name));
+                    }
+                    if !(exercise.weight.is_finite() && exercise.weight >= 0.0) {
+                        return invalid(format!("{} has an impossible weight for {}", at, exercise.name));
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// One row per set, for spreadsheets.
+pub fn to_csv(program: &TrainingProgram) -> String {
+    let mut csv = String::from("cycle,week,date,day,lift,set_type,set,reps,weight,unit,percentage,amrap,rpe\r\n");
+
+    for cycle in &program.cycles {
+        for week in &cycle.weeks {
+            for session in &week.sessions {
+                for exercise in &session.exercises {
+                    let lift = cycle
+                        .one_rep_max
+                        .lifts
+                        .iter()
+                        .find(|max| max.lift.id == exercise.lift)
+                        .map_or(exercise.name.as_str(), |max| max.lift.name.as_str());
+                    let set_type = match exercise.kind {
+                        SetKind::Warmup => "warmup",
+                        SetKind::Main => "main",
+                        SetKind::Joker => "joker",
+                        SetKind::Supplemental => "supplemental",
+                    };
+
+                    for set in 1..=exercise.sets {
+                        let row = [
+                            cycle.cycle_number.to_string(),
+                            week.week_number.to_string(),
+                            session.date.to_string(),
+                            csv_field(&session.day),
+                            csv_field(lift),
+                            set_type.to_string(),
+                            set.to_string(),
+                            exercise.reps.to_string(),
+                            exercise.weight.to_string(),
+                            program.unit.symbol().to_string(),
+                            exercise.percentage.to_string(),
+                            exercise.is_amrap.to_string(),
+                            exercise.rpe.map(|rpe| rpe.to_string()).unwrap_or_default(),
+                        ];
+                        csv.push_str(&row.join(","));
+                        csv.push_str("\r\n");
+                    }
+                }
+            }
+        }
+    }
+
+    csv
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::{Beyond531Calculator, Unit};
+    use crate::test_support;
+
+    fn generated() -> (CalculatorState, TrainingProgram) {
+        let state = test_support::state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+        let program = Beyond531Calculator::calculate_program(&state.one_rep_max(), &state.program_settings(), &TrainingLog::default());
+        (state, program)
+    }
+
+    #[test]
+    fn round_trips_program_and_inputs() {
+        let (state, program) = generated();
+
+        let imported = from_json(&to_json(&state, &TrainingLog::default(), &program)).unwrap();
+
+        assert_eq!(imported.state, state);
+        assert_eq!(imported.program, program);
+    }
+
+    #[test]
+    fn rejects_other_json_and_inconsistent_programs() {
+        let (state, mut program) = generated();
+
+        assert!(matches!(from_json("not json"), Err(ProgramFileError::Malformed(_))));
+        assert_eq!(from_json(r#"{"version": 2}"#), Err(ProgramFileError::NotAProgram));
+
+        program.unit = Unit::Pounds;
+        let err = from_json(&to_json(&state, &TrainingLog::default(), &program)).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "the program is invalid: the program is in pounds but its inputs are in kilograms"
+        );
+    }
+
+    #[test]
+    fn writes_one_csv_row_per_set() {
+        let (_, program) = generated();
+
+        let csv = to_csv(&program);
+        let rows: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(rows[0], "cycle,week,date,day,lift,set_type,set,reps,weight,unit,percentage,amrap,rpe");
+        assert!(rows[1].ends_with(",Monday,Front Squat,main,1,5,90,kg,65,false,"));
+        assert!(rows[3].contains(",Front Squat,main,3,5,"));
+        assert_eq!(csv_field("Squat, paused"), "\"Squat, paused\"");
+    }
+}
@@ -0,0 +1,212 @@
+use crate::beyond_531::{Lift, LiftCategory, SetKind, WeekKind};
+use crate::joker::StopRule;
+use crate::schedule::{Schedule, Weekday};
+
+/// One line of a prescription: `sets` x `reps` at `percentage` of the lift's max.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SetPrescription {
+    pub sets: u32,
+    pub reps: u32,
+    pub percentage: f64,
+    pub kind: SetKind,
+    pub is_amrap: bool,
+    /// Taken from the true 1RM even when the program runs off a training max.
+    pub of_one_rep_max: bool,
+    /// Appended to the lift name when rendered, e.g. "Front Squat - Single".
+    pub label: Option<String>,
+    pub stop_rule: Option<StopRule>,
+}
+
+impl SetPrescription {
+    pub fn new(sets: u32, reps: u32, percentage: f64) -> Self {
+        Self {
+            sets,
+            reps,
+            percentage,
+            kind: SetKind::Main,
+            is_amrap: false,
+            of_one_rep_max: false,
+            label: None,
+            stop_rule: None,
+        }
+    }
+
+    pub fn amrap(mut self) -> Self {
+        self.is_amrap = true;
+        self
+    }
+
+    pub fn warmup(mut self) -> Self {
+        self.kind = SetKind::Warmup;
+        self
+    }
+
+    pub fn joker(mut self) -> Self {
+        self.kind = SetKind::Joker;
+        self
+    }
+
+    pub fn until(mut self, stop_rule: StopRule) -> Self {
+        self.stop_rule = Some(stop_rule);
+        self
+    }
+
+    pub fn supplemental(mut self) -> Self {
+        self.kind = SetKind::Supplemental;
+        self
+    }
+
+    pub fn of_one_rep_max(mut self) -> Self {
+        self.of_one_rep_max = true;
+        self
+    }
+
+    pub fn labelled(mut self, label: &str) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiftTemplate {
+    pub lift: Lift,
+    pub sets: Vec<SetPrescription>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SessionTemplate {
+    pub day: String,
+    pub weekday: Weekday,
+    pub lifts: Vec<LiftTemplate>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct WeekTemplate {
+    pub week_number: u32,
+    pub kind: WeekKind,
+    pub sessions: Vec<SessionTemplate>,
+    /// Left out of the last cycle, as it only leads into the next one.
+    pub between_cycles: bool,
+}
+
+/// A complete block layout that `Beyond531Calculator` turns into concrete weights.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProgramTemplate {
+    pub name: String,
+    pub weeks: Vec<WeekTemplate>,
+}
+
+impl Default for ProgramTemplate {
+    fn default() -> Self {
+        Self::beyond_531(&Lift::defaults(), &Schedule::default())
+    }
+}
+
+impl ProgramTemplate {
+    /// The built-in Beyond 531 layout: reduced-volume work and 5/3/1 work with AMRAP top
+    /// sets for every lift, followed by a max week of singles up to 105%. `schedule` decides
+    /// which training day each lift's volume and heavy work falls on.
+    pub fn beyond_531(lifts: &[Lift], schedule: &Schedule) -> Self {
+        let weeks = (1..=4)
+            .map(|week_number| {
+                let sessions = schedule
+                    .days
+                    .iter()
+                    .enumerate()
+                    .map(|(day_index, weekday)| {
+                        let on_this_day =
+                            |day: Option<usize>| day.map(|day| schedule.day_in_week(day, week_number)) == Some(day_index);
+                        let mut lift_templates = Vec::new();
+
+                        for lift in lifts {
+                            let days = schedule.lift_days(lift.id);
+                            if on_this_day(days.volume_day) {
+                                lift_templates.push(LiftTemplate {
+                                    lift: lift.clone(),
+                                    sets: Self::beyond_531_volume_sets(lift, week_number),
+                                });
+                            }
+                            if on_this_day(days.heavy_day) {
+                                lift_templates.push(LiftTemplate {
+                                    lift: lift.clone(),
+                                    sets: Self::beyond_531_heavy_sets(week_number),
+                                });
+                            }
+                        }
+
+                        SessionTemplate {
+                            day: weekday.name().to_string(),
+                            weekday: *weekday,
+                            lifts: lift_templates,
+                        }
+                    })
+                    .collect();
+
+                let kind = if week_number == 4 { WeekKind::Max } else { WeekKind::Training };
+                WeekTemplate {
+                    week_number,
+                    kind,
+                    sessions,
+                    between_cycles: false,
+                }
+            })
+            .collect();
+
+        Self {
+            name: "Beyond 531".to_string(),
+            weeks,
+        }
+    }
+
+    // Volume day, Monday in the original split (reduced volume: 3x5 for squats & presses,
+    // 1x5 for hinges like the deadlift)
+    // Week 1 & 4: 65% to reduce CNS fatigue, Week 2 & 3: 75%
+    fn beyond_531_volume_sets(lift: &Lift, week_number: u32) -> Vec<SetPrescription> {
+        let percentage = match week_number {
+            1 | 4 => 65.0,
+            _ => 75.0,
+        };
+        let sets = match lift.category {
+            LiftCategory::Hinge => 1,
+            _ => 3,
+        };
+
+        vec![SetPrescription::new(sets, 5, percentage)]
+    }
+
+    // Heavy day, Friday in the original split (varies by week)
+    fn beyond_531_heavy_sets(week_number: u32) -> Vec<SetPrescription> {
+        match week_number {
+            // Week 1: Original 5/3/1 rep scheme (65%, 75%, 80% of real max)
+            1 => vec![
+                SetPrescription::new(1, 5, 65.0),
+                SetPrescription::new(1, 5, 75.0),
+                SetPrescription::new(1, 5, 80.0).amrap(),
+            ],
+            // Week 2: 65%, 75%, 85% of real max
+            2 => vec![
+                SetPrescription::new(1, 3, 65.0),
+                SetPrescription::new(1, 3, 75.0),
+                SetPrescription::new(1, 3, 85.0).amrap(),
+            ],
+            // Week 3: 70%, 80%, 90% of real max
+            3 => vec![
+                SetPrescription::new(1, 5, 70.0),
+                SetPrescription::new(1, 3, 80.0),
+                SetPrescription::new(1, 1, 90.0).amrap(),
+            ],
+            // Week 4: Max week - 1@65%, 1@80%, 1@90%, 1@100%, 1@105%
+            // Always relative to the true 1RM so the top singles really are "beyond" it
+            _ => vec![
+                SetPrescription::new(1, 1, 65.0).labelled("Warmup").warmup(),
+                SetPrescription::new(1, 1, 80.0).labelled("Single"),
+                SetPrescription::new(1, 1, 90.0).labelled("Single"),
+                SetPrescription::new(1, 1, 100.0).labelled("Max"),
+                SetPrescription::new(1, 1, 105.0).labelled("BEYOND!"),
+            ]
+            .into_iter()
+            .map(SetPrescription::of_one_rep_max)
+            .collect(),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::{LiftId, Unit};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum RoundingMode {
+    #[default]
+    Nearest,
+    Floor,
+    Ceil,
+}
+
+impl RoundingMode {
+    pub const ALL: [RoundingMode; 3] = [RoundingMode::Nearest, RoundingMode::Floor, RoundingMode::Ceil];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            RoundingMode::Nearest => "Nearest",
+            RoundingMode::Floor => "Round down",
+            RoundingMode::Ceil => "Round up",
+        }
+    }
+}
+
+/// Rounds a computed weight to something that can actually be loaded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rounding {
+    pub increment: f64,
+    pub mode: RoundingMode,
+}
+
+impl Rounding {
+    // Absorbs float noise such as 57.000000001 increments so floor/ceil don't skip a step
+    const EPSILON: f64 = 1e-9;
+
+    pub fn apply(&self, weight: f64) -> f64 {
+        if self.increment <= 0.0 {
+            return weight;
+        }
+
+        let steps = weight / self.increment;
+        let steps = match self.mode {
+            RoundingMode::Nearest => steps.round(),
+            RoundingMode::Floor => (steps + Self::EPSILON).floor(),
+            RoundingMode::Ceil => (steps - Self::EPSILON).ceil(),
+        };
+        steps * self.increment
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoundingPolicy {
+    pub default: Rounding,
+    pub overrides: HashMap<LiftId, Rounding>,
+}
+
+impl Default for RoundingPolicy {
+    fn default() -> Self {
+        Self::for_unit(Unit::default())
+    }
+}
+
+impl RoundingPolicy {
+    pub fn for_unit(unit: Unit) -> Self {
+        Self {
+            default: Rounding {
+                increment: unit.rounding_increment(),
+                mode: RoundingMode::Nearest,
+            },
+            overrides: HashMap::new(),
+        }
+    }
+
+    pub fn rounding_for(&self, lift: LiftId) -> Rounding {
+        self.overrides.get(&lift).copied().unwrap_or(self.default)
+    }
+
+    pub fn round(&self, lift: LiftId, weight: f64) -> f64 {
+        self.rounding_for(lift).apply(weight)
+    }
+}
@@ -0,0 +1,90 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Percent of 1RM by reps plus reps in reserve, in half-rep steps: a single at RPE 10
+/// is 100%, a single at RPE 9.5 is 97.8%, a double at RPE 10 or a single at RPE 9 is
+/// 95.5%, and so on down to 12 reps at RPE 6.
+const CHART: [f64; 31] = [
+    100.0, 97.8, 95.5, 93.9, 92.2, 90.7, 89.2, 87.8, 86.3, 85.0, 83.7, 82.4, 81.1, 79.9, 78.6, 77.4, 76.2, 75.1, 73.9,
+    72.3, 70.7, 69.4, 68.0, 66.7, 65.3, 64.0, 62.6, 61.3, 59.9, 58.6, 57.4,
+];
+
+pub const MIN_RPE: f64 = 6.0;
+pub const MAX_RPE: f64 = 10.0;
+pub const MAX_REPS: u32 = 12;
+
+/// Every RPE the chart covers, easiest first.
+pub const RPE_OPTIONS: [f64; 9] = [6.0, 6.5, 7.0, 7.5, 8.0, 8.5, 9.0, 9.5, 10.0];
+
+/// Percent of 1RM for `reps` at `rpe`, or `None` off the chart (more than 12 reps,
+/// outside RPE 6-10 or not a half step).
+pub fn percentage(reps: u32, rpe: f64) -> Option<f64> {
+    let half_steps = (MAX_RPE - rpe) * 2.0;
+    if !(1..=MAX_REPS).contains(&reps) || !(MIN_RPE..=MAX_RPE).contains(&rpe) || half_steps.fract() != 0.0 {
+        return None;
+    }
+
+    CHART.get((reps as usize - 1) * 2 + half_steps as usize).copied()
+}
+
+/// The 1RM implied by doing `reps` with `weight` at `rpe`.
+pub fn estimate_max(weight: f64, reps: u32, rpe: f64) -> Option<f64> {
+    percentage(reps, rpe).map(|percentage| weight / (percentage / 100.0))
+}
+
+pub fn reps_in_reserve(rpe: f64) -> f64 {
+    MAX_RPE - rpe
+}
+
+/// Heavy-day top sets prescribed as reps @ RPE instead of AMRAP sets at a fixed
+/// percentage. Their weights come from the chart and the lift's current max, which
+/// moves with the RPE logged on earlier top sets in the cycle.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Autoregulation {
+    pub top_set_rpe: f64,
+}
+
+impl Default for Autoregulation {
+    fn default() -> Self {
+        Self { top_set_rpe: 8.0 }
+    }
+}
+
+impl Autoregulation {
+    pub const OPTIONS: [Autoregulation; 4] = [
+        Autoregulation { top_set_rpe: 7.0 },
+        Autoregulation { top_set_rpe: 8.0 },
+        Autoregulation { top_set_rpe: 8.5 },
+        Autoregulation { top_set_rpe: 9.0 },
+    ];
+
+    pub fn name(&self) -> String {
+        format!("Reps @ RPE {} ({} in reserve)", self.top_set_rpe, reps_in_reserve(self.top_set_rpe))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_the_chart_by_reps_and_rpe() {
+        assert_eq!(percentage(1, 10.0), Some(100.0));
+        assert_eq!(percentage(2, 10.0), percentage(1, 9.0));
+        assert_eq!(percentage(5, 8.0), Some(81.1));
+        assert_eq!(percentage(12, 6.0), Some(57.4));
+        assert_eq!(percentage(13, 8.0), None);
+        assert_eq!(percentage(5, 5.5), None);
+        assert_eq!(percentage(5, 8.25), None);
+    }
+
+    #[test]
+    fn estimates_max_from_a_rated_set() {
+        let estimate = estimate_max(162.2, 5, 8.0).unwrap();
+
+        assert!((estimate - 200.0).abs() < 0.01);
+        assert_eq!(estimate_max(100.0, 0, 8.0), None);
+        assert_eq!(reps_in_reserve(8.5), 1.5);
+    }
+}
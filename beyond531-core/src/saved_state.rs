@@ -0,0 +1,255 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::beyond_531::{Lift, Unit};
+use crate::calculator_state::{CalculatorState, LiftEntry};
+use crate::estimated_max::{SetKey, TrainingLog};
+
+/// Bump this and add a step to `migrate` whenever `SavedState` changes shape.
+pub const SCHEMA_VERSION: u64 = 3;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum SavedStateError {
+    Malformed(String),
+    UnsupportedVersion(u64),
+}
+
+impl fmt::Display for SavedStateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SavedStateError::Malformed(reason) => write!(f, "saved data is malformed: {}", reason),
+            SavedStateError::UnsupportedVersion(version) => {
+                write!(f, "saved data has unsupported schema version {}", version)
+            }
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct SavedState {
+    version: u64,
+    calculator: CalculatorState,
+    amrap_results: Vec<AmrapResult>,
+    rpe_results: Vec<RpeResult>,
+}
+
+// `SetKey` is a struct, so the log is stored as a list rather than a JSON object
+#[derive(Serialize, Deserialize)]
+struct AmrapResult {
+    #[serde(flatten)]
+    key: SetKey,
+    reps: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct RpeResult {
+    #[serde(flatten)]
+    key: SetKey,
+    rpe: f64,
+}
+
+/// The saved form as a JSON string.
+pub fn encode(state: &CalculatorState, log: &TrainingLog) -> String {
+    to_value(state, log).to_string()
+}
+
+pub fn decode(json: &str) -> Result<(CalculatorState, TrainingLog), SavedStateError> {
+    let value: Value =
+        serde_json::from_str(json).map_err(|err| SavedStateError::Malformed(err.to_string()))?;
+    from_value(value)
+}
+
+/// The saved form of the inputs and log, also embedded in exported program files.
+pub fn to_value(state: &CalculatorState, log: &TrainingLog) -> Value {
+    let saved = SavedState {
+        version: SCHEMA_VERSION,
+        calculator: state.clone(),
+        amrap_results: log
+            .amrap_reps
+            .iter()
+            .map(|(&key, &reps)| AmrapResult { key, reps })
+            .collect(),
+        rpe_results: log.rpe.iter().map(|(&key, &rpe)| RpeResult { key, rpe }).collect(),
+    };
+
+    serde_json::to_value(&saved).expect("saved state always serializes")
+}
+
+/// Reads inputs and log back from `to_value`'s output, migrating older versions.
+pub fn from_value(value: Value) -> Result<(CalculatorState, TrainingLog), SavedStateError> {
+    let saved: SavedState = serde_json::from_value(migrate(value)?)
+        .map_err(|err| SavedStateError::Malformed(err.to_string()))?;
+
+    let log = TrainingLog {
+        amrap_reps: saved
+            .amrap_results
+            .into_iter()
+            .map(|result| (result.key, result.reps))
+            .collect(),
+        rpe: saved.rpe_results.into_iter().map(|result| (result.key, result.rpe)).collect(),
+    };
+
+    Ok((saved.calculator, log))
+}
+
+/// Upgrades older saved data one version at a time until it matches `SCHEMA_VERSION`.
+fn migrate(mut value: Value) -> Result<Value, SavedStateError> {
+    loop {
+        let version = value
+            .get("version")
+            .and_then(Value::as_u64)
+            .ok_or_else(|| SavedStateError::Malformed("missing schema version".to_string()))?;
+
+        value = match version {
+            SCHEMA_VERSION => return Ok(value),
+            1 => migrate_v1(value),
+            2 => migrate_v2(value),
+            _ => return Err(SavedStateError::UnsupportedVersion(version)),
+        };
+    }
+}
+
+/// Version 1 had fixed front squat/deadlift/bench fields and settings keyed by lift name;
+/// version 2 stores a lift list and keys settings by lift id.
+fn migrate_v1(mut value: Value) -> Value {
+    const V1_LIFTS: [(&str, &str); 3] = [
+        ("front_squat", "FrontSquat"),
+        ("deadlift", "Deadlift"),
+        ("bench_press", "BenchPress"),
+    ];
+
+    if let Some(calculator) = value.get_mut("calculator").and_then(Value::as_object_mut) {
+        let unit: Unit = calculator
+            .get("unit")
+            .and_then(|unit| serde_json::from_value(unit.clone()).ok())
+            .unwrap_or_default();
+        let increments = calculator.remove("lift_increments").unwrap_or_default();
+        let rounding_modes = calculator.remove("lift_rounding_modes").unwrap_or_default();
+        let mut lift_rounding_modes = Map::new();
+
+        let lifts: Vec<LiftEntry> = Lift::defaults()
+            .into_iter()
+            .zip(V1_LIFTS)
+            .map(|(lift, (field, key))| {
+                let mut entry = LiftEntry::new(lift, unit);
+                if let Some(one_rep_max) = calculator.remove(field).as_ref().and_then(Value::as_f64) {
+                    entry.one_rep_max = one_rep_max;
+                }
+                if let Some(increment) = increments.get(key).and_then(Value::as_f64) {
+                    entry.increment = increment;
+                }
+                if let Some(mode) = rounding_modes.get(key) {
+                    lift_rounding_modes.insert(entry.lift.id.0.to_string(), mode.clone());
+                }
+                entry
+            })
+            .collect();
+
+        calculator.insert("lifts".to_string(), serde_json::to_value(lifts).expect("lifts always serialize"));
+        calculator.insert("lift_rounding_modes".to_string(), Value::Object(lift_rounding_modes));
+    }
+
+    value["version"] = Value::from(2);
+    value
+}
+
+/// Version 3 adds the RPE log next to the AMRAP one; nothing was logged before it.
+fn migrate_v2(mut value: Value) -> Value {
+    if let Some(saved) = value.as_object_mut() {
+        saved.entry("rpe_results").or_insert_with(|| Value::Array(Vec::new()));
+    }
+
+    value["version"] = Value::from(3);
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::LiftId;
+    use crate::rounding::RoundingMode;
+
+    #[test]
+    fn round_trips_state_and_log() {
+        let mut state = CalculatorState::for_unit(Unit::Pounds);
+        state.lifts[0].one_rep_max = 275.0;
+        state.lift_rounding_modes.insert(LiftId(2), RoundingMode::Floor);
+        state.program_generated = true;
+        let mut log = TrainingLog::default();
+        log.record(
+            SetKey {
+                cycle_number: 1,
+                week_number: 2,
+                session_index: 1,
+                exercise_index: 2,
+            },
+            Some(6),
+        );
+        log.record_rpe(
+            SetKey {
+                cycle_number: 1,
+                week_number: 1,
+                session_index: 1,
+                exercise_index: 2,
+            },
+            Some(8.5),
+        );
+
+        assert_eq!(decode(&encode(&state, &log)), Ok((state, log)));
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let json = r#"{"version": 99, "calculator": {}, "amrap_results": []}"#;
+
+        assert_eq!(decode(json), Err(SavedStateError::UnsupportedVersion(99)));
+    }
+
+    #[test]
+    fn rejects_unversioned_data() {
+        assert!(matches!(decode("{}"), Err(SavedStateError::Malformed(_))));
+    }
+
+    #[test]
+    fn fills_missing_fields_with_defaults() {
+        let json = r#"{"version": 3, "calculator": {"cycles": 3}, "amrap_results": [], "rpe_results": []}"#;
+
+        let (state, log) = decode(json).unwrap();
+
+        assert_eq!(state.cycles, 3);
+        assert_eq!(state.lifts.len(), 3);
+        assert!(log.amrap_reps.is_empty());
+    }
+
+    #[test]
+    fn migrates_version_2_without_an_rpe_log() {
+        let json = r#"{"version": 2, "calculator": {}, "amrap_results": []}"#;
+
+        let (_, log) = decode(json).unwrap();
+
+        assert!(log.rpe.is_empty());
+    }
+
+    #[test]
+    fn migrates_fixed_lifts_from_version_1() {
+        let json = r#"{
+            "version": 1,
+            "calculator": {
+                "deadlift": 200.0,
+                "unit": "Pounds",
+                "lift_increments": {"BenchPress": 2.5},
+                "lift_rounding_modes": {"FrontSquat": "Floor"}
+            },
+            "amrap_results": []
+        }"#;
+
+        let (state, _) = decode(json).unwrap();
+
+        assert_eq!(state.lift(LiftId(2)).map(|entry| entry.one_rep_max), Some(200.0));
+        assert_eq!(state.lift(LiftId(1)).map(|entry| entry.increment), Some(10.0));
+        assert_eq!(state.lift(LiftId(3)).map(|entry| entry.increment), Some(2.5));
+        assert_eq!(state.lift_rounding_modes.get(&LiftId(1)), Some(&RoundingMode::Floor));
+    }
+}
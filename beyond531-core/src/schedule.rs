@@ -0,0 +1,271 @@
+use std::collections::HashMap;
+
+use chrono::{Datelike, Days, NaiveDate};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::{Lift, LiftId};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Weekday {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl Weekday {
+    pub const ALL: [Weekday; 7] = [
+        Weekday::Monday,
+        Weekday::Tuesday,
+        Weekday::Wednesday,
+        Weekday::Thursday,
+        Weekday::Friday,
+        Weekday::Saturday,
+        Weekday::Sunday,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Weekday::Monday => "Monday",
+            Weekday::Tuesday => "Tuesday",
+            Weekday::Wednesday => "Wednesday",
+            Weekday::Thursday => "Thursday",
+            Weekday::Friday => "Friday",
+            Weekday::Saturday => "Saturday",
+            Weekday::Sunday => "Sunday",
+        }
+    }
+
+    pub fn to_chrono(self) -> chrono::Weekday {
+        match self {
+            Weekday::Monday => chrono::Weekday::Mon,
+            Weekday::Tuesday => chrono::Weekday::Tue,
+            Weekday::Wednesday => chrono::Weekday::Wed,
+            Weekday::Thursday => chrono::Weekday::Thu,
+            Weekday::Friday => chrono::Weekday::Fri,
+            Weekday::Saturday => chrono::Weekday::Sat,
+            Weekday::Sunday => chrono::Weekday::Sun,
+        }
+    }
+}
+
+/// The date `weekday` falls on in the `week_offset`-th training week. Training weeks open
+/// on `first_day`, the earliest weekday trained, and the first one is the first such week
+/// that starts on or after `start`, so no session lands before the start date and each
+/// week's sessions stay in weekday order.
+pub fn session_date(start: NaiveDate, first_day: Weekday, week_offset: u32, weekday: Weekday) -> NaiveDate {
+    let days_from = |from: u32, to: u32| u64::from((7 + to - from) % 7);
+    let first_day = first_day.to_chrono().num_days_from_monday();
+    let week_start = start
+        + Days::new(days_from(start.weekday().num_days_from_monday(), first_day))
+        + Days::new(7 * u64::from(week_offset));
+    week_start + Days::new(days_from(first_day, weekday.to_chrono().num_days_from_monday()))
+}
+
+/// The training days a lift gets its reduced-volume and its heavy 5/3/1 work on,
+/// as indices into `Schedule::days`. `None` leaves that work out.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct LiftDays {
+    pub volume_day: Option<usize>,
+    pub heavy_day: Option<usize>,
+}
+
+/// Which weekdays are trained and which lifts go on each of them.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Schedule {
+    pub days: Vec<Weekday>,
+    /// Kept in weekday order. Lifts without an entry get volume on the earliest weekday
+    /// and heavy work on the latest.
+    pub lift_days: HashMap<LiftId, LiftDays>,
+    /// Moves every lift one training day later each week, so lifts cycle across days.
+    pub rotate: bool,
+}
+
+impl Default for Schedule {
+    /// The original Beyond 531 split: every lift on Monday (volume) and Friday (heavy).
+    fn default() -> Self {
+        Self {
+            days: vec![Weekday::Monday, Weekday::Friday],
+            lift_days: HashMap::new(),
+            rotate: false,
+        }
+    }
+}
+
+impl Schedule {
+    /// Monday/Wednesday/Friday, each lift heavy on one day and volume on the next,
+    /// rotating one day further each week.
+    pub fn three_day_rotating(lifts: &[Lift]) -> Self {
+        let days = vec![Weekday::Monday, Weekday::Wednesday, Weekday::Friday];
+        let lift_days = lifts
+            .iter()
+            .enumerate()
+            .map(|(index, lift)| {
+                let days = LiftDays {
+                    heavy_day: Some(index % days.len()),
+                    volume_day: Some((index + 1) % days.len()),
+                };
+                (lift.id, days)
+            })
+            .collect();
+
+        Self {
+            days,
+            lift_days,
+            rotate: true,
+        }
+    }
+
+    pub fn lift_days(&self, id: LiftId) -> LiftDays {
+        let by_weekday = || self.days.iter().enumerate().map(|(index, weekday)| (*weekday, index));
+        self.lift_days.get(&id).copied().unwrap_or(LiftDays {
+            volume_day: Some(by_weekday().min().map_or(0, |(_, index)| index)),
+            heavy_day: Some(by_weekday().max().map_or(0, |(_, index)| index)),
+        })
+    }
+
+    /// The index into `days` that work assigned to `day` lands on in `week_number`.
+    pub fn day_in_week(&self, day: usize, week_number: u32) -> usize {
+        if self.days.is_empty() {
+            return 0;
+        }
+        let offset = if self.rotate { week_number.saturating_sub(1) as usize } else { 0 };
+        (day.min(self.days.len() - 1) + offset) % self.days.len()
+    }
+
+    /// Adds or drops training days; new days take the first weekday not yet used and
+    /// dropped ones are the latest in the week.
+    pub fn set_day_count(&mut self, count: usize) {
+        let count = count.clamp(1, Weekday::ALL.len());
+        self.sort_days();
+        self.days.truncate(count);
+        while self.days.len() < count {
+            let Some(weekday) = Weekday::ALL.into_iter().find(|weekday| !self.days.contains(weekday)) else {
+                break;
+            };
+            self.days.push(weekday);
+            self.sort_days();
+        }
+    }
+
+    /// Puts `days` back in weekday order, moving each lift's days along with their weekdays.
+    pub fn sort_days(&mut self) {
+        let mut order: Vec<usize> = (0..self.days.len()).collect();
+        order.sort_by_key(|&index| self.days[index]);
+        let new_index = |old: usize| order.iter().position(|&index| index == old).unwrap_or(old);
+
+        for lift_days in self.lift_days.values_mut() {
+            lift_days.volume_day = lift_days.volume_day.map(new_index);
+            lift_days.heavy_day = lift_days.heavy_day.map(new_index);
+        }
+        self.days = order.iter().map(|&index| self.days[index]).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::program_template::ProgramTemplate;
+
+    fn lifts_by_day(template: &ProgramTemplate, week_number: u32) -> Vec<(String, Vec<String>)> {
+        template
+            .weeks
+            .iter()
+            .find(|week| week.week_number == week_number)
+            .unwrap()
+            .sessions
+            .iter()
+            .map(|session| {
+                let lifts = session.lifts.iter().map(|lift| lift.lift.name.clone()).collect();
+                (session.day.clone(), lifts)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn default_schedule_trains_every_lift_monday_and_friday() {
+        let template = ProgramTemplate::default();
+
+        let days = lifts_by_day(&template, 1);
+
+        assert_eq!(days.len(), 2);
+        assert_eq!(days[0].0, "Monday");
+        assert_eq!(days[1].0, "Friday");
+        assert!(days.iter().all(|(_, lifts)| lifts.len() == 3));
+    }
+
+    #[test]
+    fn rotating_schedule_moves_lifts_a_day_each_week() {
+        let lifts = Lift::defaults();
+        let schedule = Schedule::three_day_rotating(&lifts);
+        let template = ProgramTemplate::beyond_531(&lifts, &schedule);
+
+        let week_1 = lifts_by_day(&template, 1);
+        let week_2 = lifts_by_day(&template, 2);
+
+        assert_eq!(week_1[0], ("Monday".to_string(), vec!["Front Squat".to_string(), "Bench Press".to_string()]));
+        assert_eq!(week_2[1].0, "Wednesday");
+        assert_eq!(week_2[1].1, week_1[0].1);
+    }
+
+    #[test]
+    fn changing_day_count_picks_unused_weekdays_in_order() {
+        let mut schedule = Schedule::default();
+
+        schedule.set_day_count(4);
+        assert_eq!(
+            schedule.days,
+            vec![Weekday::Monday, Weekday::Tuesday, Weekday::Wednesday, Weekday::Friday]
+        );
+        assert_eq!(schedule.lift_days(LiftId(1)).heavy_day, Some(3));
+
+        schedule.set_day_count(0);
+        assert_eq!(schedule.days, vec![Weekday::Monday]);
+    }
+
+    #[test]
+    fn sorting_days_keeps_each_lift_on_its_weekday() {
+        let mut schedule = Schedule {
+            days: vec![Weekday::Friday, Weekday::Monday, Weekday::Wednesday],
+            ..Schedule::default()
+        };
+        assert_eq!(schedule.lift_days(LiftId(1)), LiftDays { volume_day: Some(1), heavy_day: Some(0) });
+        schedule.lift_days.insert(LiftId(2), LiftDays { volume_day: Some(2), heavy_day: Some(0) });
+
+        schedule.sort_days();
+
+        assert_eq!(schedule.days, vec![Weekday::Monday, Weekday::Wednesday, Weekday::Friday]);
+        assert_eq!(schedule.lift_days(LiftId(2)), LiftDays { volume_day: Some(1), heavy_day: Some(2) });
+    }
+
+    #[test]
+    fn session_dates_fall_on_the_weekday_within_each_week() {
+        // A Monday
+        let start = NaiveDate::from_ymd_opt(2026, 10, 12).unwrap();
+
+        assert_eq!(session_date(start, Weekday::Monday, 0, Weekday::Monday), start);
+        assert_eq!(session_date(start, Weekday::Monday, 0, Weekday::Friday), NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+        assert_eq!(session_date(start, Weekday::Monday, 4, Weekday::Friday), NaiveDate::from_ymd_opt(2026, 11, 13).unwrap());
+    }
+
+    #[test]
+    fn mid_week_start_waits_for_the_first_training_day() {
+        // A Wednesday, with a Monday/Friday schedule
+        let start = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+
+        let volume = session_date(start, Weekday::Monday, 0, Weekday::Monday);
+        let heavy = session_date(start, Weekday::Monday, 0, Weekday::Friday);
+
+        assert_eq!(volume, NaiveDate::from_ymd_opt(2026, 10, 19).unwrap());
+        assert_eq!(heavy, NaiveDate::from_ymd_opt(2026, 10, 23).unwrap());
+        assert_eq!(session_date(start, Weekday::Wednesday, 0, Weekday::Friday), NaiveDate::from_ymd_opt(2026, 10, 16).unwrap());
+    }
+}
@@ -0,0 +1,458 @@
+use std::fmt;
+
+use crate::beyond_531::{Lift, LiftCategory, LiftId, Unit};
+use crate::calculator_state::{CalculatorState, LiftEntry};
+use crate::rounding::RoundingMode;
+use crate::validation::{self, ValidationError};
+
+/// Maxes and settings carried in a link's `#lift=squat:140:Front%20Squat&..` fragment.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedLink {
+    pub lifts: Vec<SharedLift>,
+    pub unit: Unit,
+    pub rounding_increment: Option<f64>,
+    pub rounding_mode: Option<RoundingMode>,
+    pub training_max_percent: Option<f64>,
+    pub week: Option<u32>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SharedLift {
+    pub name: String,
+    pub category: LiftCategory,
+    pub one_rep_max: f64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ShareLinkError {
+    MissingParameter(&'static str),
+    InvalidNumber { parameter: String, value: String },
+    InvalidLift(String),
+    InvalidUnit(String),
+    InvalidRoundingMode(String),
+    InvalidWeek(String),
+    OutOfRange { parameter: &'static str, error: ValidationError },
+}
+
+impl fmt::Display for ShareLinkError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareLinkError::MissingParameter(parameter) => write!(f, "missing \"{}\"", parameter),
+            ShareLinkError::InvalidNumber { parameter, value } => {
+                write!(f, "\"{}\" must be a positive number, got \"{}\"", parameter, value)
+            }
+            ShareLinkError::InvalidLift(value) => {
+                write!(f, "\"{}\" is not a lift (expected category:max:name)", value)
+            }
+            ShareLinkError::InvalidUnit(value) => write!(f, "unknown unit \"{}\" (expected kg or lb)", value),
+            ShareLinkError::InvalidRoundingMode(value) => {
+                write!(f, "unknown rounding \"{}\" (expected nearest, floor or ceil)", value)
+            }
+            ShareLinkError::InvalidWeek(value) => write!(f, "\"{}\" is not a week number", value),
+            ShareLinkError::OutOfRange { parameter, error } => write!(f, "\"{}\" {}", parameter, error),
+        }
+    }
+}
+
+impl SharedLink {
+    pub fn from_state(state: &CalculatorState, week: u32) -> Self {
+        Self {
+            lifts: state
+                .lifts
+                .iter()
+                .map(|entry| SharedLift {
+                    name: entry.lift.name.clone(),
+                    category: entry.lift.category,
+                    one_rep_max: entry.one_rep_max,
+                })
+                .collect(),
+            unit: state.unit,
+            rounding_increment: Some(state.rounding_increment),
+            rounding_mode: Some(state.rounding_mode),
+            training_max_percent: state.use_training_max.then_some(state.training_max_percent),
+            week: Some(week),
+        }
+    }
+
+    /// Overlays the link on `state` and marks the program as generated if the inputs are
+    /// valid. Shared lifts that match a local one by name and category update it in place,
+    /// keeping its id, increment and per-lift settings; the rest are added with default
+    /// increments. Local lifts the link doesn't mention are removed along with their settings.
+    pub fn apply_to(&self, state: &mut CalculatorState) {
+        state.change_unit(self.unit);
+        let mut local = std::mem::take(&mut state.lifts);
+        let mut next_id = local.iter().map(|entry| entry.lift.id.0).max().unwrap_or(0) + 1;
+        let mut removed: Vec<LiftId> = local.iter().map(|entry| entry.lift.id).collect();
+
+        state.lifts = self
+            .lifts
+            .iter()
+            .map(|shared| {
+                let matching = local
+                    .iter()
+                    .position(|entry| entry.lift.name == shared.name && entry.lift.category == shared.category);
+                let mut entry = match matching {
+                    Some(index) => {
+                        let entry = local.remove(index);
+                        removed.retain(|id| *id != entry.lift.id);
+                        entry
+                    }
+                    None => {
+                        let lift = Lift::new(next_id, &shared.name, shared.category);
+                        next_id += 1;
+                        LiftEntry::new(lift, self.unit)
+                    }
+                };
+                entry.one_rep_max = shared.one_rep_max;
+                entry
+            })
+            .collect();
+        for id in removed {
+            state.lift_rounding_modes.remove(&id);
+            state.supplemental.templates.remove(&id);
+            state.schedule.lift_days.remove(&id);
+        }
+        if let Some(increment) = self.rounding_increment {
+            state.rounding_increment = increment;
+        }
+        if let Some(mode) = self.rounding_mode {
+            state.rounding_mode = mode;
+        }
+        state.use_training_max = self.training_max_percent.is_some();
+        if let Some(percent) = self.training_max_percent {
+            state.training_max_percent = percent;
+        }
+        state.program_generated = state.has_valid_inputs();
+    }
+
+    /// Whether opening the link would leave an already generated `state` as it is, so a
+    /// reload doesn't reapply the fragment the page wrote itself.
+    pub fn matches(&self, state: &CalculatorState) -> bool {
+        state.program_generated
+            && *self
+                == SharedLink {
+                    week: self.week,
+                    ..SharedLink::from_state(state, 1)
+                }
+    }
+
+    pub fn encode(&self) -> String {
+        let mut parameters: Vec<String> = self
+            .lifts
+            .iter()
+            .map(|lift| {
+                format!(
+                    "lift={}:{}:{}",
+                    lift.category.token(),
+                    lift.one_rep_max,
+                    encode_component(&lift.name)
+                )
+            })
+            .collect();
+        parameters.push(format!("unit={}", self.unit.symbol()));
+        if let Some(increment) = self.rounding_increment {
+            parameters.push(format!("inc={}", increment));
+        }
+        if let Some(mode) = self.rounding_mode {
+            parameters.push(format!("round={}", rounding_mode_token(mode)));
+        }
+        if let Some(percent) = self.training_max_percent {
+            parameters.push(format!("tm={}", percent));
+        }
+        if let Some(week) = self.week {
+            parameters.push(format!("week={}", week));
+        }
+        parameters.join("&")
+    }
+
+    /// Parses a fragment or query string, with or without its leading `#`/`?`.
+    /// Unknown parameters are ignored, and links from before custom lifts
+    /// (`fs`, `dl` and `bp`) still open as the default trio.
+    pub fn decode(fragment: &str) -> Result<Self, ShareLinkError> {
+        let mut lifts = Vec::new();
+        let mut front_squat = None;
+        let mut deadlift = None;
+        let mut bench_press = None;
+        let mut unit = Unit::default();
+        let mut rounding_increment = None;
+        let mut rounding_mode = None;
+        let mut training_max_percent = None;
+        let mut week = None;
+
+        let fragment = fragment.trim_start_matches(['#', '?']);
+        for pair in fragment.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            match key {
+                "lift" => lifts.push(decode_lift(value)?),
+                "fs" => front_squat = Some(parse_positive(key, value)?),
+                "dl" => deadlift = Some(parse_positive(key, value)?),
+                "bp" => bench_press = Some(parse_positive(key, value)?),
+                "unit" => {
+                    unit = match value {
+                        "kg" => Unit::Kilograms,
+                        "lb" => Unit::Pounds,
+                        _ => return Err(ShareLinkError::InvalidUnit(value.to_string())),
+                    }
+                }
+                "inc" => rounding_increment = Some(parse_positive(key, value)?),
+                "round" => {
+                    let mode = RoundingMode::ALL
+                        .into_iter()
+                        .find(|mode| rounding_mode_token(*mode) == value)
+                        .ok_or_else(|| ShareLinkError::InvalidRoundingMode(value.to_string()))?;
+                    rounding_mode = Some(mode);
+                }
+                "tm" => training_max_percent = Some(parse_positive(key, value)?),
+                "week" => {
+                    let number = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|week| *week > 0)
+                        .ok_or_else(|| ShareLinkError::InvalidWeek(value.to_string()))?;
+                    week = Some(number);
+                }
+                _ => {}
+            }
+        }
+
+        // Held to the same ranges as the form, so a link can't open a program it would refuse
+        let out_of_range = |parameter| move |error| ShareLinkError::OutOfRange { parameter, error };
+        if let Some(increment) = rounding_increment {
+            validation::check_rounding_increment(increment, unit).map_err(out_of_range("inc"))?;
+        }
+        if let Some(percent) = training_max_percent {
+            validation::check_training_max_percent(percent).map_err(out_of_range("tm"))?;
+        }
+
+        let legacy = [front_squat, deadlift, bench_press];
+        if lifts.is_empty() && legacy.iter().any(Option::is_some) {
+            for ((lift, max), parameter) in Lift::defaults().into_iter().zip(legacy).zip(["fs", "dl", "bp"]) {
+                lifts.push(SharedLift {
+                    name: lift.name,
+                    category: lift.category,
+                    one_rep_max: max.ok_or(ShareLinkError::MissingParameter(parameter))?,
+                });
+            }
+        }
+        if lifts.is_empty() {
+            return Err(ShareLinkError::MissingParameter("lift"));
+        }
+
+        Ok(Self {
+            lifts,
+            unit,
+            rounding_increment,
+            rounding_mode,
+            training_max_percent,
+            week,
+        })
+    }
+}
+
+fn rounding_mode_token(mode: RoundingMode) -> &'static str {
+    match mode {
+        RoundingMode::Nearest => "nearest",
+        RoundingMode::Floor => "floor",
+        RoundingMode::Ceil => "ceil",
+    }
+}
+
+// The name goes last so it may contain `:` itself
+fn decode_lift(value: &str) -> Result<SharedLift, ShareLinkError> {
+    let invalid = || ShareLinkError::InvalidLift(value.to_string());
+    let mut parts = value.splitn(3, ':');
+    let (Some(category), Some(max), Some(name)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(invalid());
+    };
+
+    Ok(SharedLift {
+        category: LiftCategory::from_token(category).ok_or_else(invalid)?,
+        one_rep_max: parse_positive("lift", max)?,
+        name: decode_component(name).filter(|name| !name.is_empty()).ok_or_else(invalid)?,
+    })
+}
+
+/// Percent-encodes everything but unreserved characters.
+fn encode_component(text: &str) -> String {
+    text.bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => (byte as char).to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+fn decode_component(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        match byte {
+            b'%' => {
+                let hex = std::str::from_utf8(tail.get(..2)?).ok()?;
+                bytes.push(u8::from_str_radix(hex, 16).ok()?);
+                rest = &tail[2..];
+            }
+            b'+' => {
+                bytes.push(b' ');
+                rest = tail;
+            }
+            _ => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn parse_positive(parameter: &str, value: &str) -> Result<f64, ShareLinkError> {
+    value
+        .parse::<f64>()
+        .ok()
+        .filter(|number| number.is_finite() && *number > 0.0)
+        .ok_or_else(|| ShareLinkError::InvalidNumber {
+            parameter: parameter.to_string(),
+            value: value.to_string(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schedule::Schedule;
+    use crate::supplemental::SupplementalTemplate;
+    use crate::test_support;
+
+    #[test]
+    fn round_trips_through_fragment() {
+        let mut state = CalculatorState::for_unit(Unit::Pounds);
+        state.lifts[0].one_rep_max = 275.0;
+        state.lifts[1].one_rep_max = 455.0;
+        state.lifts[2].one_rep_max = 225.5;
+        state.lifts[2].lift.name = "Bench: 2-board & pause".to_string();
+        state.rounding_mode = RoundingMode::Floor;
+        state.use_training_max = true;
+
+        let link = SharedLink::from_state(&state, 3);
+
+        assert_eq!(SharedLink::decode(&format!("#{}", link.encode())), Ok(link));
+    }
+
+    #[test]
+    fn applies_to_state_and_generates_program() {
+        let link = SharedLink::decode("fs=140&dl=200&bp=100&unit=lb&week=2").unwrap();
+        let mut state = CalculatorState::default();
+
+        link.apply_to(&mut state);
+
+        assert_eq!(state.unit, Unit::Pounds);
+        assert_eq!(state.rounding_increment, 5.0);
+        assert_eq!(state.lifts[1].lift.name, "Deadlift");
+        assert_eq!(state.lifts[1].one_rep_max, 200.0);
+        assert!(state.program_generated);
+        assert_eq!(link.week, Some(2));
+    }
+
+    #[test]
+    fn applying_a_states_own_link_keeps_its_settings() {
+        let mut state = test_support::state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+        state.lifts[1].increment = 2.5;
+        state.lift_rounding_modes.insert(LiftId(2), RoundingMode::Floor);
+        state.supplemental.templates.insert(LiftId(3), SupplementalTemplate::BoringButBig);
+        state.schedule = Schedule::three_day_rotating(&Lift::defaults());
+        state.program_generated = true;
+        let link = SharedLink::from_state(&state, 2);
+
+        let mut opened = state.clone();
+        link.apply_to(&mut opened);
+
+        assert_eq!(opened, state);
+        assert!(link.matches(&state));
+    }
+
+    #[test]
+    fn applying_a_link_drops_settings_for_lifts_it_leaves_out() {
+        let mut state = CalculatorState::default();
+        state.supplemental.templates.insert(LiftId(1), SupplementalTemplate::BoringButBig);
+        state.supplemental.templates.insert(LiftId(2), SupplementalTemplate::BoringButBig);
+        state.lifts[1].increment = 2.5;
+
+        SharedLink::decode("lift=hinge:200:Deadlift&lift=press:60:Overhead%20Press").unwrap().apply_to(&mut state);
+
+        let ids: Vec<(u32, &str)> = state.lifts.iter().map(|entry| (entry.lift.id.0, entry.lift.name.as_str())).collect();
+        assert_eq!(ids, vec![(2, "Deadlift"), (4, "Overhead Press")]);
+        assert_eq!(state.lifts[0].increment, 2.5);
+        assert_eq!(state.supplemental.templates.keys().collect::<Vec<_>>(), vec![&LiftId(2)]);
+    }
+
+    #[test]
+    fn implausible_maxes_open_without_a_program() {
+        let mut state = CalculatorState::default();
+
+        SharedLink::decode("lift=squat:9999:Squat").unwrap().apply_to(&mut state);
+
+        assert_eq!(state.lifts[0].one_rep_max, 9999.0);
+        assert!(!state.program_generated);
+    }
+
+    #[test]
+    fn decodes_custom_lifts() {
+        let link = SharedLink::decode("lift=squat:180:Back%20Squat&lift=press:60:Overhead+Press").unwrap();
+
+        assert_eq!(
+            link.lifts,
+            vec![
+                SharedLift {
+                    name: "Back Squat".to_string(),
+                    category: LiftCategory::Squat,
+                    one_rep_max: 180.0,
+                },
+                SharedLift {
+                    name: "Overhead Press".to_string(),
+                    category: LiftCategory::Press,
+                    one_rep_max: 60.0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_parameters() {
+        assert_eq!(
+            SharedLink::decode("fs=abc&dl=200&bp=100"),
+            Err(ShareLinkError::InvalidNumber {
+                parameter: "fs".to_string(),
+                value: "abc".to_string()
+            })
+        );
+        assert_eq!(
+            SharedLink::decode("fs=140&dl=200&bp=100&unit=stone"),
+            Err(ShareLinkError::InvalidUnit("stone".to_string()))
+        );
+        assert_eq!(
+            SharedLink::decode("fs=140&dl=-5&bp=100"),
+            Err(ShareLinkError::InvalidNumber {
+                parameter: "dl".to_string(),
+                value: "-5".to_string()
+            })
+        );
+        assert_eq!(
+            SharedLink::decode("fs=140&dl=200&bp=100&week=0"),
+            Err(ShareLinkError::InvalidWeek("0".to_string()))
+        );
+        assert_eq!(
+            SharedLink::decode("fs=140&dl=200&bp=100&tm=300").unwrap_err().to_string(),
+            "\"tm\" must be between 50% and 100%"
+        );
+        assert_eq!(
+            SharedLink::decode("fs=140&dl=200&bp=100&inc=0.0001&unit=lb").unwrap_err().to_string(),
+            "\"inc\" must be between 0.25lb and 25lb"
+        );
+        assert_eq!(SharedLink::decode("fs=140&dl=200"), Err(ShareLinkError::MissingParameter("bp")));
+        assert_eq!(SharedLink::decode("unit=kg"), Err(ShareLinkError::MissingParameter("lift")));
+        assert_eq!(
+            SharedLink::decode("lift=curl:40:Curl"),
+            Err(ShareLinkError::InvalidLift("curl:40:Curl".to_string()))
+        );
+    }
+}
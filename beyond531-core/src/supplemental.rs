@@ -0,0 +1,250 @@
+use std::collections::{BTreeSet, HashMap};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::{LiftId, SetKind};
+use crate::program_template::{ProgramTemplate, SetPrescription};
+use crate::schedule::Schedule;
+
+/// Extra volume done after the main sets, following the classic 5/3/1 supplemental templates.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum SupplementalTemplate {
+    BoringButBig,
+    FirstSetLast,
+    SecondSetLast,
+    Widowmaker,
+}
+
+impl SupplementalTemplate {
+    pub const ALL: [SupplementalTemplate; 4] = [
+        SupplementalTemplate::BoringButBig,
+        SupplementalTemplate::FirstSetLast,
+        SupplementalTemplate::SecondSetLast,
+        SupplementalTemplate::Widowmaker,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            SupplementalTemplate::BoringButBig => "Boring But Big (5x10)",
+            SupplementalTemplate::FirstSetLast => "First Set Last (5x5)",
+            SupplementalTemplate::SecondSetLast => "Second Set Last (5x5)",
+            SupplementalTemplate::Widowmaker => "Widowmaker (1x20)",
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            SupplementalTemplate::BoringButBig => "BBB",
+            SupplementalTemplate::FirstSetLast => "FSL",
+            SupplementalTemplate::SecondSetLast => "SSL",
+            SupplementalTemplate::Widowmaker => "Widowmaker",
+        }
+    }
+
+    /// `ramp` is the lift's main 5/3/1 sets for the week, in order. Everything but BBB
+    /// takes its weight from the ramp, so those give `None` in a week without one.
+    pub fn prescription(&self, ramp: &[SetPrescription]) -> Option<SetPrescription> {
+        let (sets, reps, percentage) = match self {
+            SupplementalTemplate::BoringButBig => (5, 10, 50.0),
+            SupplementalTemplate::FirstSetLast => (5, 5, ramp.first()?.percentage),
+            SupplementalTemplate::SecondSetLast => (5, 5, ramp.get(1)?.percentage),
+            SupplementalTemplate::Widowmaker => (1, 20, ramp.first()?.percentage),
+        };
+
+        Some(
+            SetPrescription::new(sets, reps, percentage)
+                .supplemental()
+                .labelled(self.label()),
+        )
+    }
+}
+
+/// Which lifts get supplemental work, and in which weeks and on which days. With no days
+/// chosen, or none that trains the lift that week, it goes with the lift's heavy sets.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct SupplementalPlan {
+    pub templates: HashMap<LiftId, SupplementalTemplate>,
+    pub weeks: BTreeSet<u32>,
+    pub days: BTreeSet<String>,
+}
+
+impl Default for SupplementalPlan {
+    /// The heavy day of the three 5/3/1 weeks; the max week is left alone.
+    fn default() -> Self {
+        Self {
+            templates: HashMap::new(),
+            weeks: BTreeSet::from([1, 2, 3]),
+            days: BTreeSet::new(),
+        }
+    }
+}
+
+impl SupplementalPlan {
+    /// Appends each lift's supplemental sets after its main sets on the chosen weeks and days.
+    pub fn apply_to(&self, template: &mut ProgramTemplate, schedule: &Schedule) {
+        for week in template
+            .weeks
+            .iter_mut()
+            .filter(|week| self.weeks.contains(&week.week_number))
+        {
+            for (&lift, supplemental) in &self.templates {
+                // The ramp comes from whichever session has the lift's AMRAP top set
+                let ramp: Vec<SetPrescription> = week
+                    .sessions
+                    .iter()
+                    .flat_map(|session| &session.lifts)
+                    .filter(|lift_template| lift_template.lift.id == lift)
+                    .map(|lift_template| &lift_template.sets)
+                    .find(|sets| sets.iter().any(|set| set.is_amrap))
+                    .map(|sets| sets.iter().filter(|set| set.kind == SetKind::Main).cloned().collect())
+                    .unwrap_or_default();
+                let Some(prescription) = supplemental.prescription(&ramp) else {
+                    continue;
+                };
+
+                let on_chosen_day = week.sessions.iter().any(|session| {
+                    self.days.contains(&session.day) && session.lifts.iter().any(|lift_template| lift_template.lift.id == lift)
+                });
+                let heavy_day = schedule
+                    .lift_days(lift)
+                    .heavy_day
+                    .and_then(|day| schedule.days.get(schedule.day_in_week(day, week.week_number)))
+                    .copied();
+
+                for lift_template in week
+                    .sessions
+                    .iter_mut()
+                    .filter(|session| {
+                        if on_chosen_day {
+                            self.days.contains(&session.day)
+                        } else {
+                            Some(session.weekday) == heavy_day
+                        }
+                    })
+                    .flat_map(|session| &mut session.lifts)
+                    .filter(|lift_template| lift_template.lift.id == lift)
+                {
+                    lift_template.sets.push(prescription.clone());
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::Lift;
+    use crate::schedule::Weekday;
+
+    fn supplemental_sets(template: &ProgramTemplate, week_number: u32, day: &str) -> Vec<SetPrescription> {
+        template
+            .weeks
+            .iter()
+            .filter(|week| week.week_number == week_number)
+            .flat_map(|week| &week.sessions)
+            .filter(|session| session.day == day)
+            .flat_map(|session| &session.lifts)
+            .flat_map(|lift_template| &lift_template.sets)
+            .filter(|set| set.kind == SetKind::Supplemental)
+            .cloned()
+            .collect()
+    }
+
+    #[test]
+    fn adds_bbb_to_chosen_weeks_and_days_only() {
+        let lifts = Lift::defaults();
+        let mut template = ProgramTemplate::default();
+        let plan = SupplementalPlan {
+            templates: HashMap::from([(lifts[0].id, SupplementalTemplate::BoringButBig)]),
+            ..Default::default()
+        };
+
+        plan.apply_to(&mut template, &Schedule::default());
+
+        let friday = supplemental_sets(&template, 1, "Friday");
+        assert_eq!(friday.len(), 1);
+        assert_eq!((friday[0].sets, friday[0].reps, friday[0].percentage), (5, 10, 50.0));
+        assert_eq!(friday[0].label.as_deref(), Some("BBB"));
+        assert!(supplemental_sets(&template, 1, "Monday").is_empty());
+        assert!(supplemental_sets(&template, 4, "Friday").is_empty());
+    }
+
+    #[test]
+    fn follows_the_heavy_day_of_a_custom_schedule() {
+        let lifts = Lift::defaults();
+        let schedule = Schedule {
+            days: vec![Weekday::Tuesday, Weekday::Thursday],
+            ..Schedule::default()
+        };
+        let mut template = ProgramTemplate::beyond_531(&lifts, &schedule);
+        let plan = SupplementalPlan {
+            templates: HashMap::from([(lifts[0].id, SupplementalTemplate::BoringButBig)]),
+            ..Default::default()
+        };
+
+        plan.apply_to(&mut template, &schedule);
+
+        assert_eq!(supplemental_sets(&template, 1, "Thursday").len(), 1);
+        assert!(supplemental_sets(&template, 1, "Tuesday").is_empty());
+    }
+
+    #[test]
+    fn chosen_days_without_the_lift_fall_back_to_its_heavy_day() {
+        let lifts = Lift::defaults();
+        let schedule = Schedule {
+            days: vec![Weekday::Monday, Weekday::Thursday],
+            ..Schedule::default()
+        };
+        let mut template = ProgramTemplate::beyond_531(&lifts, &schedule);
+        let plan = SupplementalPlan {
+            templates: HashMap::from([(lifts[0].id, SupplementalTemplate::BoringButBig)]),
+            days: BTreeSet::from(["Friday".to_string()]),
+            ..Default::default()
+        };
+
+        plan.apply_to(&mut template, &schedule);
+
+        assert_eq!(supplemental_sets(&template, 2, "Thursday").len(), 1);
+    }
+
+    #[test]
+    fn first_and_second_set_last_follow_the_weekly_ramp() {
+        let lifts = Lift::defaults();
+        let mut template = ProgramTemplate::default();
+        let plan = SupplementalPlan {
+            templates: HashMap::from([
+                (lifts[0].id, SupplementalTemplate::FirstSetLast),
+                (lifts[1].id, SupplementalTemplate::SecondSetLast),
+            ]),
+            ..Default::default()
+        };
+
+        plan.apply_to(&mut template, &Schedule::default());
+
+        let percentages: Vec<f64> = supplemental_sets(&template, 3, "Friday")
+            .iter()
+            .map(|set| set.percentage)
+            .collect();
+        assert_eq!(percentages, vec![70.0, 80.0]);
+    }
+
+    #[test]
+    fn ramp_based_templates_skip_weeks_without_a_top_set() {
+        let lifts = Lift::defaults();
+        let mut template = ProgramTemplate::default();
+        let plan = SupplementalPlan {
+            templates: HashMap::from([(lifts[0].id, SupplementalTemplate::Widowmaker)]),
+            weeks: BTreeSet::from([4]),
+            ..Default::default()
+        };
+
+        plan.apply_to(&mut template, &Schedule::default());
+
+        assert!(supplemental_sets(&template, 4, "Friday").is_empty());
+    }
+}
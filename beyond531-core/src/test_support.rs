@@ -0,0 +1,29 @@
+//! Fixtures shared by the unit and integration tests.
+
+use chrono::NaiveDate;
+
+use crate::beyond_531::{Beyond531Calculator, Lift, LiftMax, OneRepMax, ProgramSettings, TrainingProgram, Unit};
+use crate::calculator_state::CalculatorState;
+use crate::estimated_max::TrainingLog;
+
+/// The default lifts in `unit` with `maxes` entered in order, starting on Monday 5 January 2026.
+pub fn state_with_maxes(unit: Unit, maxes: [f64; 3]) -> CalculatorState {
+    let mut state = CalculatorState::for_unit(unit);
+    for (entry, one_rep_max) in state.lifts.iter_mut().zip(maxes) {
+        entry.one_rep_max = one_rep_max;
+    }
+    state.calendar.start_date = NaiveDate::from_ymd_opt(2026, 1, 5);
+    state
+}
+
+/// The default lifts, each with a 100kg max, run through `settings` with nothing logged.
+pub fn program(settings: &ProgramSettings) -> TrainingProgram {
+    let one_rep_max = OneRepMax {
+        lifts: Lift::defaults()
+            .into_iter()
+            .map(|lift| LiftMax { lift, weight: 100.0 })
+            .collect(),
+        unit: Unit::Kilograms,
+    };
+    Beyond531Calculator::calculate_program(&one_rep_max, settings, &TrainingLog::default())
+}
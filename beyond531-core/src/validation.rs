@@ -0,0 +1,241 @@
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use crate::beyond_531::{Lift, OneRepMax, Unit};
+
+/// Why an entered number can't be used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ValidationError {
+    NotANumber,
+    NotFinite,
+    BelowMinimum { minimum: f64, unit: Unit },
+    AbovePlausibleMaximum { maximum: f64, unit: Unit },
+    NotAMultipleOf { increment: f64, unit: Unit },
+    PercentOutOfRange { minimum: f64, maximum: f64 },
+    IncrementOutOfRange { minimum: f64, maximum: f64, unit: Unit },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValidationError::NotANumber => write!(f, "enter a number"),
+            ValidationError::NotFinite => write!(f, "enter a finite number"),
+            ValidationError::BelowMinimum { minimum, unit } => write!(f, "must be at least {}{}", minimum, unit),
+            ValidationError::AbovePlausibleMaximum { maximum, unit } => {
+                write!(f, "more than {}{} is beyond any recorded lift", maximum, unit)
+            }
+            ValidationError::NotAMultipleOf { increment, unit } => {
+                write!(f, "must be a multiple of {}{}", increment, unit)
+            }
+            ValidationError::PercentOutOfRange { minimum, maximum } => {
+                write!(f, "must be between {}% and {}%", minimum, maximum)
+            }
+            ValidationError::IncrementOutOfRange { minimum, maximum, unit } => {
+                write!(f, "must be between {}{} and {}{}", minimum, unit, maximum, unit)
+            }
+        }
+    }
+}
+
+/// Parses what was typed into a numeric field.
+pub fn parse_number(text: &str) -> Result<f64, ValidationError> {
+    let value: f64 = text.trim().parse().map_err(|_| ValidationError::NotANumber)?;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(ValidationError::NotFinite)
+    }
+}
+
+/// Training max percentages of the 1RM the calculator accepts.
+pub const TRAINING_MAX_PERCENT: RangeInclusive<f64> = 50.0..=100.0;
+
+pub fn check_training_max_percent(percent: f64) -> Result<(), ValidationError> {
+    if !percent.is_finite() {
+        Err(ValidationError::NotFinite)
+    } else if TRAINING_MAX_PERCENT.contains(&percent) {
+        Ok(())
+    } else {
+        Err(ValidationError::PercentOutOfRange {
+            minimum: *TRAINING_MAX_PERCENT.start(),
+            maximum: *TRAINING_MAX_PERCENT.end(),
+        })
+    }
+}
+
+/// Rounding increments the calculator accepts, in either unit: a pair of the smallest
+/// microplates up to a pair of large plates.
+pub const ROUNDING_INCREMENT: RangeInclusive<f64> = 0.25..=25.0;
+
+pub fn check_rounding_increment(increment: f64, unit: Unit) -> Result<(), ValidationError> {
+    if !increment.is_finite() {
+        Err(ValidationError::NotFinite)
+    } else if ROUNDING_INCREMENT.contains(&increment) {
+        Ok(())
+    } else {
+        Err(ValidationError::IncrementOutOfRange {
+            minimum: *ROUNDING_INCREMENT.start(),
+            maximum: *ROUNDING_INCREMENT.end(),
+            unit,
+        })
+    }
+}
+
+/// The range a 1RM has to fall in, and the smallest jump microplates allow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WeightLimits {
+    pub minimum: f64,
+    pub maximum: f64,
+    pub increment: f64,
+    pub unit: Unit,
+}
+
+impl WeightLimits {
+    pub fn for_unit(unit: Unit) -> Self {
+        match unit {
+            Unit::Kilograms => Self {
+                minimum: 10.0,
+                maximum: 550.0,
+                increment: 0.25,
+                unit,
+            },
+            Unit::Pounds => Self {
+                minimum: 20.0,
+                maximum: 1200.0,
+                increment: 0.5,
+                unit,
+            },
+        }
+    }
+
+    pub fn check(&self, weight: f64) -> Result<(), ValidationError> {
+        let steps = weight / self.increment;
+        if !weight.is_finite() {
+            Err(ValidationError::NotFinite)
+        } else if weight < self.minimum {
+            Err(ValidationError::BelowMinimum {
+                minimum: self.minimum,
+                unit: self.unit,
+            })
+        } else if weight > self.maximum {
+            Err(ValidationError::AbovePlausibleMaximum {
+                maximum: self.maximum,
+                unit: self.unit,
+            })
+        } else if (steps - steps.round()).abs() > 1e-6 {
+            Err(ValidationError::NotAMultipleOf {
+                increment: self.increment,
+                unit: self.unit,
+            })
+        } else {
+            Ok(())
+        }
+    }
+
+    /// The nearest weight that passes the increment check.
+    pub fn tidy(&self, weight: f64) -> f64 {
+        (weight / self.increment).round() * self.increment
+    }
+}
+
+/// A lift whose 1RM failed validation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LiftError {
+    pub lift: Lift,
+    pub error: ValidationError,
+}
+
+impl fmt::Display for LiftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.lift.name, self.error)
+    }
+}
+
+impl OneRepMax {
+    /// Every lift whose max is out of range for the unit, in lift order.
+    pub fn validate(&self) -> Vec<LiftError> {
+        let limits = WeightLimits::for_unit(self.unit);
+        self.lifts
+            .iter()
+            .filter_map(|max| {
+                let error = limits.check(max.weight).err()?;
+                Some(LiftError {
+                    lift: max.lift.clone(),
+                    error,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::LiftMax;
+
+    fn maxes(unit: Unit, weights: [f64; 3]) -> OneRepMax {
+        OneRepMax {
+            lifts: Lift::defaults()
+                .into_iter()
+                .zip(weights)
+                .map(|(lift, weight)| LiftMax { lift, weight })
+                .collect(),
+            unit,
+        }
+    }
+
+    #[test]
+    fn parses_numbers_and_rejects_the_rest() {
+        assert_eq!(parse_number(" 142.5 "), Ok(142.5));
+        assert_eq!(parse_number("abc"), Err(ValidationError::NotANumber));
+        assert_eq!(parse_number(""), Err(ValidationError::NotANumber));
+        assert_eq!(parse_number("inf"), Err(ValidationError::NotFinite));
+        assert_eq!(parse_number("NaN"), Err(ValidationError::NotFinite));
+    }
+
+    #[test]
+    fn training_max_percent_stays_within_range() {
+        assert_eq!(check_training_max_percent(90.0), Ok(()));
+        assert_eq!(check_training_max_percent(100.0), Ok(()));
+        assert_eq!(
+            check_training_max_percent(0.0).unwrap_err().to_string(),
+            "must be between 50% and 100%"
+        );
+        assert!(check_training_max_percent(300.0).is_err());
+    }
+
+    #[test]
+    fn rounding_increment_stays_within_range() {
+        assert_eq!(check_rounding_increment(2.5, Unit::Kilograms), Ok(()));
+        assert_eq!(
+            check_rounding_increment(0.0, Unit::Kilograms).unwrap_err().to_string(),
+            "must be between 0.25kg and 25kg"
+        );
+        assert!(check_rounding_increment(0.001, Unit::Pounds).is_err());
+        assert_eq!(check_rounding_increment(f64::NAN, Unit::Pounds), Err(ValidationError::NotFinite));
+    }
+
+    #[test]
+    fn reports_each_lift_out_of_range() {
+        let errors = maxes(Unit::Kilograms, [0.0, 600.0, 100.1]).validate();
+
+        let messages: Vec<String> = errors.iter().map(LiftError::to_string).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Front Squat: must be at least 10kg",
+                "Deadlift: more than 550kg is beyond any recorded lift",
+                "Bench Press: must be a multiple of 0.25kg",
+            ]
+        );
+        assert!(maxes(Unit::Pounds, [225.0, 405.5, 185.0]).validate().is_empty());
+    }
+
+    #[test]
+    fn nan_maxes_are_not_finite() {
+        let errors = maxes(Unit::Kilograms, [f64::NAN, 100.0, 100.0]).validate();
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].error, ValidationError::NotFinite);
+    }
+}
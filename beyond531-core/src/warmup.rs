@@ -0,0 +1,146 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::beyond_531::{Exercise, Lift, SetKind};
+
+/// One step of a warm-up ramp; `percentage` of the lift's max, or the empty bar when `None`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WarmupStep {
+    pub percentage: Option<f64>,
+    pub reps: u32,
+}
+
+impl WarmupStep {
+    const fn bar(reps: u32) -> Self {
+        Self { percentage: None, reps }
+    }
+
+    const fn at(percentage: f64, reps: u32) -> Self {
+        Self {
+            percentage: Some(percentage),
+            reps,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum WarmupRamp {
+    #[default]
+    Standard,
+    Quick,
+}
+
+impl WarmupRamp {
+    pub const ALL: [WarmupRamp; 2] = [WarmupRamp::Standard, WarmupRamp::Quick];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            WarmupRamp::Standard => "Standard (bar×10, 40%×5, 50%×5, 60%×3)",
+            WarmupRamp::Quick => "Quick (bar×10, 50%×5, 70%×3)",
+        }
+    }
+
+    pub fn steps(&self) -> &'static [WarmupStep] {
+        const STANDARD: [WarmupStep; 4] = [
+            WarmupStep::bar(10),
+            WarmupStep::at(40.0, 5),
+            WarmupStep::at(50.0, 5),
+            WarmupStep::at(60.0, 3),
+        ];
+        const QUICK: [WarmupStep; 3] = [WarmupStep::bar(10), WarmupStep::at(50.0, 5), WarmupStep::at(70.0, 3)];
+
+        match self {
+            WarmupRamp::Standard => &STANDARD,
+            WarmupRamp::Quick => &QUICK,
+        }
+    }
+}
+
+/// Ramp scheme plus the bar it starts from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WarmupSettings {
+    pub ramp: WarmupRamp,
+    pub bar_weight: f64,
+}
+
+impl WarmupSettings {
+    /// Warm-up rows leading into `first_work_set`. Steps that would not be lighter than the
+    /// work set are dropped, as are percentage steps that round down to the empty bar.
+    pub fn exercises(&self, lift: &Lift, base: f64, first_work_set: f64, round: impl Fn(f64) -> f64) -> Vec<Exercise> {
+        let mut exercises: Vec<Exercise> = Vec::new();
+
+        for step in self.ramp.steps() {
+            let (weight, label) = match step.percentage {
+                Some(percentage) => (round(base * (percentage / 100.0)), "Warm-up"),
+                None => (self.bar_weight, "Empty bar"),
+            };
+            let heavier_than_previous = exercises.last().is_none_or(|previous| weight > previous.weight);
+            let lighter_than_work = weight < first_work_set;
+            let above_bar = step.percentage.is_none() || weight > self.bar_weight;
+            if !(heavier_than_previous && lighter_than_work && above_bar) {
+                continue;
+            }
+
+            exercises.push(Exercise {
+                name: format!("{} - {}", lift.name, label),
+                lift: lift.id,
+                kind: SetKind::Warmup,
+                sets: 1,
+                reps: step.reps,
+                weight,
+                percentage: step.percentage.unwrap_or_else(|| (weight / base * 100.0).round()),
+                is_amrap: false,
+                stop_rule: None,
+                rpe: None,
+            });
+        }
+
+        exercises
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::beyond_531::LiftCategory;
+
+    fn ramp(settings: WarmupSettings, base: f64, first_work_set: f64) -> Vec<(u32, f64)> {
+        let lift = Lift::new(1, "Back Squat", LiftCategory::Squat);
+        settings
+            .exercises(&lift, base, first_work_set, |weight| (weight / 2.5).round() * 2.5)
+            .iter()
+            .map(|exercise| (exercise.reps, exercise.weight))
+            .collect()
+    }
+
+    #[test]
+    fn standard_ramp_starts_with_the_empty_bar() {
+        let settings = WarmupSettings {
+            ramp: WarmupRamp::Standard,
+            bar_weight: 20.0,
+        };
+
+        assert_eq!(ramp(settings, 150.0, 97.5), vec![(10, 20.0), (5, 60.0), (5, 75.0), (3, 90.0)]);
+    }
+
+    #[test]
+    fn skips_steps_at_or_above_the_work_set() {
+        let settings = WarmupSettings {
+            ramp: WarmupRamp::Quick,
+            bar_weight: 20.0,
+        };
+
+        assert_eq!(ramp(settings, 150.0, 97.5), vec![(10, 20.0), (5, 75.0)]);
+    }
+
+    #[test]
+    fn light_lifts_skip_steps_below_the_bar() {
+        let settings = WarmupSettings {
+            ramp: WarmupRamp::Standard,
+            bar_weight: 20.0,
+        };
+
+        assert_eq!(ramp(settings, 50.0, 32.5), vec![(10, 20.0), (5, 25.0), (3, 30.0)]);
+    }
+}
@@ -0,0 +1,280 @@
+//! Properties every generated program must have, plus golden output for reference maxes.
+//! Run with `UPDATE_GOLDEN=1` to rewrite the files in `tests/golden` after an intended change.
+
+use std::fs;
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+
+use beyond531_core::beyond_531::{Beyond531Calculator, Cycle, Exercise, LiftCategory, TrainingProgram, Unit, Week, WeekKind};
+use beyond531_core::block::BlockStructure;
+use beyond531_core::calculator_state::CalculatorState;
+use beyond531_core::estimated_max::TrainingLog;
+use beyond531_core::rounding::RoundingMode;
+use beyond531_core::schedule::{Schedule, Weekday};
+use beyond531_core::test_support::state_with_maxes;
+
+/// Small deterministic generator so the property checks cover many inputs without a
+/// dependency, and a failure always reproduces.
+struct Inputs(u64);
+
+impl Inputs {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn pick<T: Copy>(&mut self, options: &[T]) -> T {
+        options[(self.next() % options.len() as u64) as usize]
+    }
+
+    /// A max the validation accepts: a multiple of the unit's microplate step within range.
+    fn one_rep_max(&mut self, unit: Unit) -> f64 {
+        let (minimum, steps, step) = match unit {
+            Unit::Kilograms => (20.0, 1600, 0.25),
+            Unit::Pounds => (45.0, 1800, 0.5),
+        };
+        minimum + (self.next() % steps) as f64 * step
+    }
+
+    fn state(&mut self) -> CalculatorState {
+        let unit = self.pick(&[Unit::Kilograms, Unit::Pounds]);
+        let mut state = CalculatorState::for_unit(unit);
+        for entry in &mut state.lifts {
+            entry.one_rep_max = self.one_rep_max(unit);
+        }
+        state.use_training_max = self.pick(&[false, true]);
+        state.training_max_percent = self.pick(&[85.0, 90.0]);
+        state.rounding_mode = self.pick(&RoundingMode::ALL);
+        state.cycles = self.pick(&[1, 2, 3]);
+        state.block = self.pick(&BlockStructure::ALL);
+        state.use_training_max |= state.block.needs_training_max();
+        if self.pick(&[false, true]) {
+            let lifts: Vec<_> = state.lifts.iter().map(|entry| entry.lift.clone()).collect();
+            state.schedule = Schedule::three_day_rotating(&lifts);
+        }
+        state.calendar.start_date = NaiveDate::from_ymd_opt(2026, 1, 5);
+        state
+    }
+}
+
+fn generate(state: &CalculatorState) -> TrainingProgram {
+    Beyond531Calculator::calculate_program(&state.one_rep_max(), &state.program_settings(), &TrainingLog::default())
+}
+
+/// Runs `check` against a few hundred generated inputs.
+fn for_many_inputs(check: impl Fn(&CalculatorState, &TrainingProgram)) {
+    let mut inputs = Inputs(0x5eed_0531);
+    for _ in 0..300 {
+        let state = inputs.state();
+        check(&state, &generate(&state));
+    }
+}
+
+/// Every exercise in a cycle, with the week it falls in.
+fn exercises(cycle: &Cycle) -> impl Iterator<Item = (&Week, &Exercise)> {
+    cycle.weeks.iter().flat_map(|week| {
+        week.sessions
+            .iter()
+            .flat_map(|session| &session.exercises)
+            .map(move |exercise| (week, exercise))
+    })
+}
+
+#[test]
+fn every_weight_is_a_multiple_of_the_rounding_increment() {
+    for_many_inputs(|state, program| {
+        for cycle in &program.cycles {
+            for (week, exercise) in exercises(cycle) {
+                let steps = exercise.weight / state.rounding_increment;
+                assert!(
+                    (steps - steps.round()).abs() < 1e-9,
+                    "cycle {} week {}: {} is not a multiple of {}",
+                    cycle.cycle_number,
+                    week.week_number,
+                    exercise.weight,
+                    state.rounding_increment
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn weights_follow_the_percentage_of_their_max() {
+    for_many_inputs(|state, program| {
+        let factor = if state.use_training_max { state.training_max_percent / 100.0 } else { 1.0 };
+        for cycle in &program.cycles {
+            for (week, exercise) in exercises(cycle) {
+                let one_rep_max = cycle.one_rep_max.for_lift(exercise.lift).unwrap();
+                // Max week singles are always taken from the true 1RM
+                let base = if week.kind == WeekKind::Max && exercise.reps == 1 { one_rep_max } else { one_rep_max * factor };
+                let exact = base * exercise.percentage / 100.0;
+                assert!(
+                    (exercise.weight - exact).abs() <= state.rounding_increment + 1e-9,
+                    "{} in week {} is {} but {}% of {} is {}",
+                    exercise.name,
+                    week.week_number,
+                    exercise.weight,
+                    exercise.percentage,
+                    base,
+                    exact
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn percentages_match_the_program_overview() {
+    for_many_inputs(|state, program| {
+        for cycle in &program.cycles {
+            for max in &state.one_rep_max().lifts {
+                // (sets, reps, percentage, AMRAP) per training day, volume day first
+                let days = |week: u32| -> Vec<Vec<(u32, u32, f64, bool)>> {
+                    let mut days: Vec<Vec<_>> = cycle.weeks[week as usize - 1]
+                        .sessions
+                        .iter()
+                        .map(|session| {
+                            session
+                                .exercises
+                                .iter()
+                                .filter(|exercise| exercise.lift == max.lift.id)
+                                .map(|exercise| (exercise.sets, exercise.reps, exercise.percentage, exercise.is_amrap))
+                                .collect::<Vec<_>>()
+                        })
+                        .filter(|sets| !sets.is_empty())
+                        .collect();
+                    days.sort_by_key(Vec::len);
+                    days
+                };
+                // Volume day: 3x5 (1x5 for hinges) at 65% on weeks 1 & 4 and 75% on weeks 2 & 3
+                let volume_sets = if max.lift.category == LiftCategory::Hinge { 1 } else { 3 };
+                let volume = |percentage| vec![(volume_sets, 5, percentage, false)];
+
+                assert_eq!(days(1), vec![volume(65.0), vec![(1, 5, 65.0, false), (1, 5, 75.0, false), (1, 5, 80.0, true)]]);
+                assert_eq!(days(2), vec![volume(75.0), vec![(1, 3, 65.0, false), (1, 3, 75.0, false), (1, 3, 85.0, true)]]);
+                assert_eq!(days(3), vec![volume(75.0), vec![(1, 5, 70.0, false), (1, 3, 80.0, false), (1, 1, 90.0, true)]]);
+                assert_eq!(
+                    days(4),
+                    vec![
+                        volume(65.0),
+                        vec![(1, 1, 65.0, false), (1, 1, 80.0, false), (1, 1, 90.0, false), (1, 1, 100.0, false), (1, 1, 105.0, false)]
+                    ]
+                );
+            }
+        }
+    });
+}
+
+#[test]
+fn one_amrap_per_lift_in_each_training_week() {
+    for_many_inputs(|state, program| {
+        for cycle in &program.cycles {
+            for week in &cycle.weeks {
+                for max in &state.one_rep_max().lifts {
+                    let amraps = exercises(cycle)
+                        .filter(|(in_week, exercise)| {
+                            in_week.week_number == week.week_number && exercise.lift == max.lift.id && exercise.is_amrap
+                        })
+                        .count();
+                    let expected = if week.kind == WeekKind::Training { 1 } else { 0 };
+                    assert_eq!(amraps, expected, "{} in week {}", max.lift.name, week.week_number);
+                }
+            }
+        }
+    });
+}
+
+#[test]
+fn amraps_fall_on_friday_with_the_default_schedule() {
+    let mut inputs = Inputs(0x0ff1ce);
+    for _ in 0..50 {
+        let mut state = inputs.state();
+        state.schedule = Schedule::default();
+        let program = generate(&state);
+
+        for week in program.cycles.iter().flat_map(|cycle| &cycle.weeks) {
+            for session in &week.sessions {
+                let has_amrap = session.exercises.iter().any(|exercise| exercise.is_amrap);
+                assert_eq!(has_amrap, week.kind == WeekKind::Training && session.day == Weekday::Friday.name());
+            }
+        }
+    }
+}
+
+#[test]
+fn weights_never_drop_within_a_lifts_ramp() {
+    for_many_inputs(|_, program| {
+        for session in program.cycles.iter().flat_map(|cycle| &cycle.weeks).flat_map(|week| &week.sessions) {
+            let ramps = session.exercises.chunk_by(|a, b| a.lift == b.lift);
+            for ramp in ramps {
+                for pair in ramp.windows(2) {
+                    assert!(
+                        pair[0].weight <= pair[1].weight,
+                        "{} on {}: {} then {}",
+                        pair[0].name,
+                        session.date,
+                        pair[0].weight,
+                        pair[1].weight
+                    );
+                }
+            }
+        }
+    });
+}
+
+#[test]
+fn matches_golden_output_for_reference_maxes() {
+    let mut kilograms = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    kilograms.cycles = 2;
+
+    let mut pounds = state_with_maxes(Unit::Pounds, [315.0, 455.0, 225.0]);
+    pounds.use_training_max = true;
+
+    let mut seventh_week = pounds.clone();
+    seventh_week.block = BlockStructure::SeventhWeekTrainingMaxTest;
+    seventh_week.cycles = 2;
+
+    for (name, state) in [
+        ("kilograms", kilograms),
+        ("pounds_training_max", pounds),
+        ("pounds_seventh_week_test", seventh_week),
+    ] {
+        assert_golden(name, &render(&generate(&state)));
+    }
+}
+
+fn render(program: &TrainingProgram) -> String {
+    let mut output = String::new();
+    for cycle in &program.cycles {
+        for week in &cycle.weeks {
+            output.push_str(&format!("Cycle {} - {}\n", cycle.cycle_number, week.title()));
+            for session in &week.sessions {
+                output.push_str(&format!("  {} {}\n", session.day, session.date));
+                for exercise in &session.exercises {
+                    output.push_str(&format!("    {}\n", exercise.describe(program.unit)));
+                }
+            }
+        }
+    }
+    output
+}
+
+fn assert_golden(name: &str, actual: &str) {
+    let path: PathBuf = [env!("CARGO_MANIFEST_DIR"), "tests", "golden", &format!("{}.txt", name)].iter().collect();
+    if std::env::var_os("UPDATE_GOLDEN").is_some() {
+        fs::write(&path, actual).unwrap();
+        return;
+    }
+
+    let expected = fs::read_to_string(&path).unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+    assert!(
+        expected == actual,
+        "{} differs from the generated program; rerun with UPDATE_GOLDEN=1 if the change is intended\n\n{}",
+        path.display(),
+        actual
+    );
+}
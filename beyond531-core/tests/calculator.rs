@@ -0,0 +1,240 @@
+use chrono::{Datelike, NaiveDate, Weekday};
+
+use beyond531_core::beyond_531::{Beyond531Calculator, Exercise, LiftId, SetKind, TrainingProgram, Unit, WeekKind};
+use beyond531_core::block::BlockStructure;
+use beyond531_core::calculator_state::CalculatorState;
+use beyond531_core::estimated_max::{SetKey, TrainingLog};
+use beyond531_core::rpe::{self, Autoregulation};
+use beyond531_core::share_link::SharedLink;
+use beyond531_core::test_support::state_with_maxes;
+use beyond531_core::warmup::WarmupRamp;
+
+fn generate(state: &CalculatorState, log: &TrainingLog) -> TrainingProgram {
+    Beyond531Calculator::calculate_program(&state.one_rep_max(), &state.program_settings(), log)
+}
+
+/// `lift`'s AMRAP or RPE top set in `week` of the first cycle, and the key to log it under.
+fn top_set(program: &TrainingProgram, week_number: u32, lift: LiftId) -> (Exercise, SetKey) {
+    let week = &program.cycles[0].weeks[week_number as usize - 1];
+    week.sessions
+        .iter()
+        .enumerate()
+        .flat_map(|(session_index, session)| {
+            let keys = SetKey::for_session(1, week_number, session_index, session);
+            session.exercises.iter().zip(keys)
+        })
+        .find_map(|(exercise, key)| {
+            let top_set = exercise.is_amrap || exercise.rpe.is_some();
+            (exercise.lift == lift && top_set).then(|| (exercise.clone(), key.unwrap()))
+        })
+        .expect("every lift has a top set")
+}
+
+#[test]
+fn generates_a_dated_four_week_block() {
+    let program = generate(&state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]), &TrainingLog::default());
+
+    let cycle = &program.cycles[0];
+    assert_eq!(program.cycles.len(), 1);
+    assert_eq!(cycle.weeks.iter().map(|week| week.week_number).collect::<Vec<_>>(), vec![1, 2, 3, 4]);
+    for (week_index, week) in cycle.weeks.iter().enumerate() {
+        let monday = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap() + chrono::Days::new(7 * week_index as u64);
+        assert_eq!(week.sessions[0].date, monday);
+        assert!(week.sessions.iter().all(|session| !session.exercises.is_empty()));
+        assert!(week.sessions.iter().all(|session| matches!(session.date.weekday(), Weekday::Mon | Weekday::Fri)));
+    }
+}
+
+#[test]
+fn extra_week_closes_every_cycle() {
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.cycles = 2;
+    state.block = BlockStructure::DeloadAfterMaxWeek;
+    let program = generate(&state, &TrainingLog::default());
+
+    let start = NaiveDate::from_ymd_opt(2026, 1, 5).unwrap();
+    for (cycle_index, cycle) in program.cycles.iter().enumerate() {
+        let kinds: Vec<WeekKind> = cycle.weeks.iter().map(|week| week.kind).collect();
+        assert_eq!(kinds.last(), Some(&WeekKind::Deload));
+        assert_eq!(kinds.len(), 5);
+
+        let deload = cycle.weeks.last().unwrap();
+        let monday = start + chrono::Days::new(7 * (cycle_index as u64 * 5 + 4));
+        assert_eq!(deload.sessions.iter().map(|session| session.date).collect::<Vec<_>>(), vec![monday, monday + chrono::Days::new(4)]);
+    }
+    assert_eq!(program.cycles[1].weeks[0].sessions[0].date, start + chrono::Days::new(35));
+}
+
+#[test]
+fn seventh_week_only_runs_between_cycles() {
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.use_training_max = true;
+    state.block = BlockStructure::SeventhWeekTrainingMaxTest;
+    state.cycles = 2;
+    let program = generate(&state, &TrainingLog::default());
+
+    let kinds = |cycle: usize| program.cycles[cycle].weeks.iter().map(|week| week.kind).collect::<Vec<_>>();
+    assert_eq!(kinds(0).last(), Some(&WeekKind::TrainingMaxTest));
+    assert_eq!(kinds(1).len(), 4);
+    assert_eq!(kinds(1).last(), Some(&WeekKind::Max));
+    // The second cycle starts the week after the test
+    assert_eq!(
+        program.cycles[1].weeks[0].sessions[0].date,
+        NaiveDate::from_ymd_opt(2026, 1, 5).unwrap() + chrono::Days::new(35)
+    );
+}
+
+#[test]
+fn warm_up_ramp_leads_into_the_max_weeks_own_warm_up_single() {
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.warmup_ramp = Some(WarmupRamp::Quick);
+    let program = generate(&state, &TrainingLog::default());
+
+    let friday = program.cycles[0].weeks[3].sessions.last().unwrap();
+    let deadlift: Vec<(SetKind, f64)> = friday
+        .exercises
+        .iter()
+        .filter(|exercise| exercise.lift == LiftId(2))
+        .map(|exercise| (exercise.kind, exercise.weight))
+        .collect();
+
+    // bar, 50%, then the template's 65% single; the 70% step would land above it
+    assert_eq!(
+        deadlift[..4],
+        [(SetKind::Warmup, 20.0), (SetKind::Warmup, 100.0), (SetKind::Warmup, 130.0), (SetKind::Main, 160.0)]
+    );
+    assert!(deadlift.windows(2).all(|pair| pair[0].1 < pair[1].1));
+}
+
+#[test]
+fn training_max_lowers_every_percentage_set() {
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    let from_one_rep_max = generate(&state, &TrainingLog::default());
+    state.use_training_max = true;
+    state.training_max_percent = 90.0;
+    let from_training_max = generate(&state, &TrainingLog::default());
+
+    let weights = |program: &TrainingProgram| -> Vec<f64> {
+        program.cycles[0]
+            .weeks
+            .iter()
+            .flat_map(|week| &week.sessions)
+            .flat_map(|session| &session.exercises)
+            .map(|exercise| exercise.weight)
+            .collect()
+    };
+    let pairs: Vec<(f64, f64)> = weights(&from_one_rep_max).into_iter().zip(weights(&from_training_max)).collect();
+
+    assert!(!pairs.is_empty());
+    assert!(pairs.iter().all(|(one_rep_max, training_max)| training_max <= one_rep_max));
+    assert!(pairs.iter().any(|(one_rep_max, training_max)| training_max < one_rep_max));
+}
+
+#[test]
+fn logged_amrap_reps_set_the_next_cycles_max() {
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.cycles = 2;
+    let program = generate(&state, &TrainingLog::default());
+    let (top_set, key) = top_set(&program, 1, LiftId(2));
+
+    let mut log = TrainingLog::default();
+    log.record(key, Some(8));
+    let program = generate(&state, &log);
+
+    let estimate = state.e1rm_formula.estimate(top_set.weight, 8).unwrap();
+    let next = &program.cycles[1].one_rep_max;
+    assert_eq!(next.for_lift(LiftId(2)), Some((estimate * 10.0).round() / 10.0));
+    assert_eq!(next.for_lift(LiftId(1)), Some(140.0 + state.lifts[0].increment));
+    assert_eq!(next.for_lift(LiftId(3)), Some(100.0 + state.lifts[2].increment));
+}
+
+#[test]
+fn rpe_top_sets_follow_the_effort_logged_on_earlier_ones() {
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.autoregulation = Some(Autoregulation { top_set_rpe: 8.0 });
+    let program = generate(&state, &TrainingLog::default());
+
+    let (week_one, key) = top_set(&program, 1, LiftId(2));
+    assert_eq!(week_one.describe(state.unit), "Deadlift: 1x5 @ RPE 8 (162.5kg)");
+    assert!(!week_one.is_amrap);
+    assert_eq!(top_set(&program, 2, LiftId(2)).0.weight, 172.5);
+
+    // The week 1 top set was harder than planned, so week 2 comes down to match
+    let mut log = TrainingLog::default();
+    log.record_rpe(key, Some(9.5));
+    let program = generate(&state, &log);
+
+    let current_max = rpe::estimate_max(162.5, 5, 9.5).unwrap();
+    let week_two = top_set(&program, 2, LiftId(2)).0;
+    assert_eq!(week_two.weight, (current_max * 0.863 / 2.5).round() * 2.5);
+    assert_eq!(week_two.weight, 165.0);
+    assert_eq!(top_set(&program, 2, LiftId(1)).0, top_set(&generate(&state, &TrainingLog::default()), 2, LiftId(1)).0);
+}
+
+#[test]
+fn percentages_are_shown_to_the_nearest_whole_percent() {
+    let state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    let (mut exercise, _) = top_set(&generate(&state, &TrainingLog::default()), 1, LiftId(2));
+    exercise.percentage = 86.7;
+
+    assert_eq!(exercise.intensity(), "87%");
+    assert_eq!(exercise.describe(state.unit), "Deadlift: 1x5+ @ 160kg (87%)");
+
+    exercise.rpe = Some(8.5);
+    assert_eq!(exercise.intensity(), "RPE 8.5");
+}
+
+#[test]
+fn shared_link_reproduces_the_program() {
+    let mut state = state_with_maxes(Unit::Kilograms, [142.5, 210.0, 102.5]);
+    state.use_training_max = true;
+
+    let link = SharedLink::decode(&SharedLink::from_state(&state, 2).encode()).unwrap();
+    let mut opened = state_with_maxes(Unit::Kilograms, [0.0; 3]);
+    link.apply_to(&mut opened);
+
+    assert_eq!(generate(&opened, &TrainingLog::default()), generate(&state, &TrainingLog::default()));
+}
+
+#[test]
+fn blocks_generation_until_every_max_is_plausible() {
+    assert_eq!(
+        state_with_maxes(Unit::Kilograms, [140.0, 0.0, 100.0]).generate_blocker().as_deref(),
+        Some("Enter a 1RM for every lift")
+    );
+    assert_eq!(
+        state_with_maxes(Unit::Kilograms, [140.0, 700.0, 100.0]).generate_blocker().as_deref(),
+        Some("Deadlift: more than 550kg is beyond any recorded lift")
+    );
+    assert_eq!(state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]).generate_blocker(), None);
+
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.use_training_max = true;
+    state.training_max_percent = 0.0;
+    assert_eq!(state.generate_blocker().as_deref(), Some("Training Max: must be between 50% and 100%"));
+
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.rounding_increment = 0.0;
+    assert_eq!(state.generate_blocker().as_deref(), Some("Rounding increment: must be between 0.25kg and 25kg"));
+
+    // The 7th-week sets are of the training max, so they'd be 5 reps at the true 1RM without one
+    let mut state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    state.block = BlockStructure::SeventhWeekTrainingMaxTest;
+    assert_eq!(state.generate_blocker().as_deref(), Some("7th week: training max test needs a Training Max"));
+    state.use_training_max = true;
+    assert_eq!(state.generate_blocker(), None);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn exported_program_imports_unchanged() {
+    use beyond531_core::program_file;
+
+    let state = state_with_maxes(Unit::Kilograms, [140.0, 200.0, 100.0]);
+    let program = generate(&state, &TrainingLog::default());
+
+    let imported = program_file::from_json(&program_file::to_json(&state, &TrainingLog::default(), &program)).unwrap();
+
+    assert_eq!(imported.state, state);
+    assert_eq!(imported.program, program);
+}
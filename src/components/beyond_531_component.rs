@@ -1,42 +1,241 @@
+use std::collections::HashMap;
+
 use leptos::*;
-use crate::beyond_531::{OneRepMax, Beyond531Calculator as Calculator};
-use crate::components::{InputField, TrainingProgramDisplay};
+use chrono::{Local, Utc};
+use crate::beyond_531::{Beyond531Calculator as Calculator, LiftId, Unit};
+use crate::block::BlockStructure;
+use crate::calculator_state::CalculatorState;
+use crate::calendar;
+use crate::components::{
+    AssistanceInput, CalendarInput, InputField, LiftInput, PlateInventoryInput, PrintSheet, ScheduleInput, SelectField, TextField,
+    ToggleField, TrainingProgramDisplay,
+};
+use crate::estimated_max::{E1rmFormula, TrainingLog};
+use crate::joker::{JokerSettings, StopRule};
+use crate::rounding::RoundingMode;
+use crate::share_link::{self, SharedLink};
+use crate::download::download;
+use crate::pdf;
+use crate::program_file;
+use crate::storage;
+use crate::supplemental::SupplementalTemplate;
+use crate::validation::{
+    check_rounding_increment, check_training_max_percent, ValidationError, ROUNDING_INCREMENT, TRAINING_MAX_PERCENT,
+};
+use crate::rpe::Autoregulation;
+use crate::warmup::WarmupRamp;
 
 #[component]
-pub fn Beyond531Calculator() -> impl IntoView {
-    let (front_squat_1rm, set_front_squat_1rm) = create_signal(0.0);
-    let (deadlift_1rm, set_deadlift_1rm) = create_signal(0.0);
-    let (bench_press_1rm, set_bench_press_1rm) = create_signal(0.0);
-    let (program_generated, set_program_generated) = create_signal(false);
+pub fn Beyond531Calculator(
+    #[prop(default = CalculatorState::default())] initial_state: CalculatorState,
+    #[prop(default = TrainingLog::default())] initial_log: TrainingLog,
+    #[prop(default = 1)] initial_week: u32,
+    #[prop(default = None)] notice: Option<String>,
+) -> impl IntoView {
+    let state = create_rw_signal(starting_today(initial_state));
+    let notice = create_rw_signal(notice);
+    let training_log = create_rw_signal(initial_log);
+    let selected_week = create_rw_signal(initial_week);
+
+    let lift_ids = create_memo(move |_| state.with(|s| s.lifts.iter().map(|entry| entry.lift.id).collect::<Vec<_>>()));
+    let lift_name = move |id: LiftId| {
+        Signal::derive(move || state.with(|s| s.lift(id).map(|entry| entry.lift.name.clone()).unwrap_or_default()))
+    };
+    let unit = create_memo(move |_| state.with(|s| s.unit));
+    let (program_generated, set_program_generated) = create_slice(state, |s| s.program_generated, |s, v| s.program_generated = v);
+    let (use_training_max, set_use_training_max) = create_slice(state, |s| s.use_training_max, |s, v| s.use_training_max = v);
+    let (training_max_percent, set_training_max_percent) = create_slice(state, |s| s.training_max_percent, |s, v| s.training_max_percent = v);
+    let (rounding_increment, set_rounding_increment) = create_slice(state, |s| s.rounding_increment, |s, v| s.rounding_increment = v);
+    let (rounding_mode, set_rounding_mode) = create_slice(state, |s| s.rounding_mode, |s, v| s.rounding_mode = v);
+    let (plate_inventory, set_plate_inventory) = create_slice(state, |s| s.plate_inventory.clone(), |s, v| s.plate_inventory = v);
+    let (cycles, set_cycles) = create_slice(state, |s| s.cycles as f64, |s, v: f64| s.cycles = v.max(1.0) as u32);
+    let (block, set_block) = create_slice(state, |s| s.block, |s, v| s.block = v);
+    let (e1rm_formula, set_e1rm_formula) = create_slice(state, |s| s.e1rm_formula, |s, v| s.e1rm_formula = v);
+    let (use_jokers, set_use_jokers) = create_slice(state, |s| s.jokers.jokers, |s, v| s.jokers.jokers = v);
+    let (beyond_extensions, set_beyond_extensions) = create_slice(state, |s| s.jokers.beyond_extensions, |s, v| s.jokers.beyond_extensions = v);
+    let (joker_sets, set_joker_sets) = create_slice(state, |s| s.jokers.sets as f64, |s, v: f64| s.jokers.sets = v.max(1.0) as u32);
+    let (joker_jump, set_joker_jump) = create_slice(
+        state,
+        |s| s.jokers.jump_percent,
+        |s, v: f64| s.jokers.jump_percent = v.clamp(JokerSettings::MIN_JUMP, JokerSettings::MAX_JUMP),
+    );
+    let (schedule, set_schedule) = create_slice(state, |s| s.schedule.clone(), |s, v| s.schedule = v);
+    let (calendar_settings, set_calendar_settings) = create_slice(state, |s| s.calendar, |s, v| s.calendar = v);
+    let (athlete_name, set_athlete_name) = create_slice(state, |s| s.athlete_name.clone(), |s, v| s.athlete_name = v);
+    let lifts = create_memo(move |_| state.with(|s| s.lifts.iter().map(|entry| entry.lift.clone()).collect::<Vec<_>>()));
+    let (assistance, set_assistance) = create_slice(state, |s| s.assistance.clone(), |s, v| s.assistance = v);
+    let (warmup_ramp, set_warmup_ramp) = create_slice(state, |s| s.warmup_ramp, |s, v| s.warmup_ramp = v);
+    let (autoregulation, set_autoregulation) = create_slice(state, |s| s.autoregulation, |s, v| s.autoregulation = v);
+    let (stop_rule, set_stop_rule) = create_slice(state, |s| s.jokers.stop_rule, |s, v| s.jokers.stop_rule = v);
+    // Per-lift slices; a removed lift reads as a default entry until its row is disposed
+    let lift_entry = move |id: LiftId| create_slice(
+        state,
+        move |s| s.lift(id).cloned().unwrap_or_default(),
+        move |s, entry| if let Some(current) = s.lift_mut(id) { *current = entry },
+    );
+    let lift_rounding_mode = move |id: LiftId| create_slice(
+        state,
+        move |s| s.lift_rounding_modes.get(&id).copied(),
+        move |s, mode: Option<RoundingMode>| match mode {
+            Some(mode) => { s.lift_rounding_modes.insert(id, mode); }
+            None => { s.lift_rounding_modes.remove(&id); }
+        },
+    );
+    let lift_increment = move |id: LiftId| create_slice(
+        state,
+        move |s| s.lift(id).map_or(0.0, |entry| entry.increment),
+        move |s, increment| if let Some(entry) = s.lift_mut(id) { entry.increment = increment },
+    );
+    let supplemental_template = move |id: LiftId| create_slice(
+        state,
+        move |s| s.supplemental.templates.get(&id).copied(),
+        move |s, template: Option<SupplementalTemplate>| match template {
+            Some(template) => { s.supplemental.templates.insert(id, template); }
+            None => { s.supplemental.templates.remove(&id); }
+        },
+    );
+    let supplemental_week = move |week: u32| create_slice(
+        state,
+        move |s| s.supplemental.weeks.contains(&week),
+        move |s, enabled| if enabled { s.supplemental.weeks.insert(week); } else { s.supplemental.weeks.remove(&week); },
+    );
+    // Slices need `Copy` closures, so the day toggles are built from a derived signal instead
+    let supplemental_day = move |day: String| {
+        let enabled = Signal::derive({
+            let day = day.clone();
+            move || state.with(|s| s.supplemental.days.contains(&day))
+        });
+        let set_enabled = SignalSetter::map(move |enabled| state.update(|s| {
+            if enabled { s.supplemental.days.insert(day.clone()); } else { s.supplemental.days.remove(&day); }
+        }));
+        (enabled, set_enabled)
+    };
+
+    // Persist every change so a refresh picks up where the user left off
+    create_effect(move |_| {
+        state.with(|state| training_log.with(|log| storage::save(state, log)));
+    });
     
-    let has_valid_inputs = create_memo(move |_| {
-        front_squat_1rm.get() > 0.0 && deadlift_1rm.get() > 0.0 && bench_press_1rm.get() > 0.0
+    // 1RM fields holding text that isn't a number; the state keeps their last good value
+    let unparsed_maxes = create_rw_signal(HashMap::<LiftId, ValidationError>::new());
+    let one_rep_max_errors = create_memo(move |_| {
+        state.with(|s| s.one_rep_max().validate().into_iter().map(|err| (err.lift.id, err.error)).collect::<HashMap<_, _>>())
+    });
+    let generate_blocker = create_memo(move |_| {
+        let unparsed = unparsed_maxes.with(|unparsed| {
+            state.with(|s| {
+                s.lifts.iter().find_map(|entry| unparsed.get(&entry.lift.id).map(|err| format!("{}: {}", entry.lift.name, err)))
+            })
+        });
+        unparsed.or_else(|| state.with(CalculatorState::generate_blocker))
+    });
+    let has_valid_inputs = create_memo(move |_| state.with(CalculatorState::has_valid_inputs));
+
+    // Keep the page URL a shareable link to whatever program is on screen
+    create_effect(move |_| {
+        let link = (program_generated.get() && has_valid_inputs.get())
+            .then(|| state.with(|state| SharedLink::from_state(state, selected_week.get())));
+        share_link::write_to_location(link.as_ref());
+    });
+
+    let settings = create_memo(move |_| state.with(CalculatorState::program_settings));
+    let template_weeks = create_memo(move |_| {
+        settings.with(|settings| settings.template.weeks.iter().map(|week| week.week_number).collect::<Vec<_>>())
+    });
+    let template_days = create_memo(move |_| {
+        settings.with(|settings| {
+            let mut days: Vec<String> = Vec::new();
+            for session in settings.template.weeks.iter().flat_map(|week| &week.sessions) {
+                if !days.contains(&session.day) {
+                    days.push(session.day.clone());
+                }
+            }
+            days
+        })
     });
     
     let training_program = create_memo(move |_| {
         if program_generated.get() && has_valid_inputs.get() {
-            let one_rep_max = OneRepMax {
-                front_squat: front_squat_1rm.get(),
-                deadlift: deadlift_1rm.get(),
-                bench_press: bench_press_1rm.get(),
-            };
-            leptos::logging::log!("Recalculating program with: squat={}, deadlift={}, bench={}", 
-                one_rep_max.front_squat, one_rep_max.deadlift, one_rep_max.bench_press);
-            Some(Calculator::calculate_program(&one_rep_max))
+            let one_rep_max = state.with(CalculatorState::one_rep_max);
+            leptos::logging::log!("Recalculating program for {} lifts", one_rep_max.lifts.len());
+            Some(settings.with(|settings| {
+                training_log.with(|log| Calculator::calculate_program(&one_rep_max, settings, log))
+            }))
         } else {
             None
         }
     });
     
+    let change_unit = move |new_unit: Unit| {
+        state.update(|state| state.change_unit(new_unit));
+    };
+
     let generate_program = move |_| {
         set_program_generated.set(true);
     };
     
-    let reset_calculator = move |_| {
-        set_front_squat_1rm.set(0.0);
-        set_deadlift_1rm.set(0.0);
-        set_bench_press_1rm.set(0.0);
-        set_program_generated.set(false);
+    let clear_saved_data = move |_| {
+        storage::clear();
+        unparsed_maxes.set(HashMap::new());
+        training_log.set(TrainingLog::default());
+        state.set(starting_today(CalculatorState::for_unit(unit.get_untracked())));
+    };
+
+    let export_calendar = move |_| {
+        if let Some(program) = training_program.get_untracked() {
+            let ics = calendar::to_ics(&program, &calendar_settings.get_untracked(), Utc::now().naive_utc());
+            download("beyond-531.ics", "text/calendar", ics.as_bytes());
+        }
+    };
+
+    let export_json = move |_| {
+        if let Some(program) = training_program.get_untracked() {
+            let json = state.with_untracked(|state| {
+                training_log.with_untracked(|log| program_file::to_json(state, log, &program))
+            });
+            download("beyond-531.json", "application/json", json.as_bytes());
+        }
+    };
+
+    let export_csv = move |_| {
+        if let Some(program) = training_program.get_untracked() {
+            download("beyond-531.csv", "text/csv", program_file::to_csv(&program).as_bytes());
+        }
+    };
+
+    let export_pdf = move |_| {
+        if let Some(program) = training_program.get_untracked() {
+            download("beyond-531.pdf", "application/pdf", &pdf::program_pdf(&program, &athlete_name.get_untracked()));
+        }
+    };
+
+    let import_program = move |ev: ev::Event| {
+        let input: web_sys::HtmlInputElement = event_target(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        // Clear the picker so choosing the same file again still fires a change
+        input.set_value("");
+
+        spawn_local(async move {
+            let json = wasm_bindgen_futures::JsFuture::from(file.text()).await.ok().and_then(|text| text.as_string());
+            let imported = match json.map(|json| program_file::from_json(&json)) {
+                Some(Ok(imported)) => imported,
+                Some(Err(err)) => return notice.set(Some(format!("Couldn't import {}: {}", file.name(), err))),
+                None => return notice.set(Some(format!("Couldn't read {}", file.name()))),
+            };
+
+            let mut imported_state = imported.state;
+            imported_state.program_generated = true;
+            let regenerated = Calculator::calculate_program(&imported_state.one_rep_max(), &imported_state.program_settings(), &imported.log);
+            notice.set(Some(if regenerated == imported.program {
+                format!("Imported {}.", file.name())
+            } else {
+                format!("Imported {}. This version of the calculator works out different weights from its inputs, so the program below has been regenerated.", file.name())
+            }));
+            training_log.set(imported.log);
+            state.set(starting_today(imported_state));
+        });
     };
     
     view! {
@@ -45,65 +244,318 @@ pub fn Beyond531Calculator() -> impl IntoView {
                 <h1>"Beyond 531 Training Calculator"</h1>
                 <div class="calculator-info">
                     <div class="program-info">
-                        "4-week progressive strength program"
+                        {move || format!("{}-week progressive strength program", template_weeks.with(Vec::len))}
+                    </div>
+                    <div class="unit-toggle">
+                        {[Unit::Kilograms, Unit::Pounds].into_iter().map(|option| view! {
+                            <button
+                                class=move || format!("unit-option {}", if unit.get() == option { "active" } else { "" })
+                                on:click=move |_| change_unit(option)
+                            >
+                                {option.symbol()}
+                            </button>
+                        }).collect_view()}
                     </div>
-                    <button class="reset-button" on:click=reset_calculator>
-                        "Reset"
+                    <label class="import-button">
+                        "Import program"
+                        <input type="file" accept=".json,application/json" on:change=import_program />
+                    </label>
+                    <button
+                        class="print-button"
+                        disabled=move || training_program.with(Option::is_none)
+                        title=move || training_program.with(Option::is_none).then_some("Generate a program to print it")
+                        on:click=move |_| {
+                            if let Some(window) = web_sys::window() {
+                                let _ = window.print();
+                            }
+                        }
+                    >
+                        "Print program"
+                    </button>
+                    <button class="reset-button" on:click=clear_saved_data>
+                        "Clear saved data"
                     </button>
                 </div>
             </div>
             
+            {move || notice.get().map(|notice| view! { <div class="notice">{notice}</div> })}
+
             <div class="input-section">
-                <h2>"Enter your 1 Rep Max (1RM) in kilograms"</h2>
+                <h2>{move || format!("Enter your 1 Rep Max (1RM) in {}", unit.get().name())}</h2>
                 {move || {
-                    if !has_valid_inputs.get() {
-                        view! {
-                            <div class="input-prompt">
-                                <p class="instruction">"Please enter your 1RM for all three exercises:"</p>
-                            </div>
-                        }
-                    } else if !program_generated.get() {
+                    if program_generated.get() && has_valid_inputs.get() {
                         view! {
                             <div class="input-complete">
-                                <p class="success">"✓ All 1RM values entered!"</p>
-                                <button class="generate-button" on:click=generate_program>
-                                    "Generate Training Program"
-                                </button>
+                                <p class="success">"✓ Your training program is ready below!"</p>
                             </div>
                         }
                     } else {
+                        let blocker = generate_blocker.get();
                         view! {
-                            <div class="input-complete">
-                                <p class="success">"✓ Your training program is ready below!"</p>
+                            <div class=if blocker.is_some() { "input-prompt" } else { "input-complete" }>
+                                {match &blocker {
+                                    Some(reason) => view! { <p class="instruction">{reason.clone()}</p> },
+                                    None => view! { <p class="success">"✓ All 1RM values entered!"</p> },
+                                }}
+                                <button
+                                    class="generate-button"
+                                    disabled=blocker.is_some()
+                                    title=blocker.clone()
+                                    on:click=generate_program
+                                >
+                                    "Generate Training Program"
+                                </button>
                             </div>
                         }
                     }
                 }}
-                <div class="input-grid">
-                    <InputField
-                        label="Front Squat 1RM (kg)".to_string()
-                        value=front_squat_1rm
-                        on_change=set_front_squat_1rm
-                        step=2.5
-                        min=10.0
+                <div class="settings-grid">
+                    <TextField label="Athlete name".to_string() value=athlete_name on_change=set_athlete_name />
+                </div>
+
+                <div class="lift-list">
+                    <For
+                        each=move || lift_ids.get()
+                        key=|id| *id
+                        children=move |id| {
+                            let (lift, set_lift) = lift_entry(id);
+                            // Nothing entered yet isn't worth a red message; the generate button says so instead
+                            let one_rep_max_error = Signal::derive(move || {
+                                let unentered = state.with(|s| s.lift(id).is_some_and(|entry| entry.one_rep_max == 0.0));
+                                one_rep_max_errors.with(|errors| errors.get(&id).copied()).filter(|_| !unentered)
+                            });
+                            let on_one_rep_max_error = SignalSetter::map(move |err: Option<ValidationError>| {
+                                unparsed_maxes.update(|unparsed| match err {
+                                    Some(err) => { unparsed.insert(id, err); }
+                                    None => { unparsed.remove(&id); }
+                                });
+                            });
+                            view! {
+                                <LiftInput
+                                    lift=lift
+                                    on_change=set_lift
+                                    unit=unit
+                                    on_remove=move |_| {
+                                        unparsed_maxes.update(|unparsed| { unparsed.remove(&id); });
+                                        state.update(|s| s.remove_lift(id));
+                                    }
+                                    one_rep_max_error=one_rep_max_error
+                                    on_one_rep_max_error=on_one_rep_max_error
+                                />
+                            }
+                        }
+                    />
+                    <button class="add-button" on:click=move |_| state.update(|s| { s.add_lift(); })>
+                        "+ Add lift"
+                    </button>
+                </div>
+
+                <div class="settings-grid">
+                    <ToggleField
+                        label="Base percentages on a Training Max".to_string()
+                        checked=use_training_max
+                        on_change=set_use_training_max
                     />
-                    
+
+                    {move || use_training_max.get().then(|| view! {
+                        <InputField
+                            label="Training Max (% of 1RM)".to_string()
+                            value=training_max_percent
+                            on_change=set_training_max_percent
+                            step=1.0
+                            min=*TRAINING_MAX_PERCENT.start()
+                            error=Signal::derive(move || check_training_max_percent(training_max_percent.get()).err())
+                        />
+                    })}
+                </div>
+
+                <div class="settings-grid">
                     <InputField
-                        label="Deadlift 1RM (kg)".to_string()
-                        value=deadlift_1rm
-                        on_change=set_deadlift_1rm
-                        step=2.5
-                        min=10.0
+                        label=Signal::derive(move || format!("Rounding increment ({})", unit.get()))
+                        value=rounding_increment
+                        on_change=set_rounding_increment
+                        step=0.25
+                        min=*ROUNDING_INCREMENT.start()
+                        error=Signal::derive(move || check_rounding_increment(rounding_increment.get(), unit.get()).err())
+                    />
+
+                    <SelectField
+                        label="Rounding mode".to_string()
+                        options=RoundingMode::ALL.iter().map(|mode| (*mode, mode.name().to_string())).collect()
+                        value=rounding_mode
+                        on_change=set_rounding_mode
+                    />
+
+                    <For
+                        each=move || lift_ids.get()
+                        key=|id| *id
+                        children=move |id| {
+                            let (mode, set_mode) = lift_rounding_mode(id);
+                            let options = std::iter::once((None, "Same as above".to_string()))
+                                .chain(RoundingMode::ALL.iter().map(|mode| (Some(*mode), mode.name().to_string())))
+                                .collect();
+                            view! {
+                                <SelectField
+                                    label=Signal::derive(move || format!("{} rounding", lift_name(id).get()))
+                                    options=options
+                                    value=mode
+                                    on_change=set_mode
+                                />
+                            }
+                        }
                     />
-                    
+                </div>
+
+                <div class="settings-grid">
                     <InputField
-                        label="Bench Press 1RM (kg)".to_string()
-                        value=bench_press_1rm
-                        on_change=set_bench_press_1rm
-                        step=2.5
-                        min=10.0
+                        label="Cycles to generate".to_string()
+                        value=cycles
+                        on_change=set_cycles
+                        step=1.0
+                        min=1.0
+                    />
+
+                    <SelectField
+                        label="After the max week".to_string()
+                        options=BlockStructure::ALL.iter().map(|block| (*block, block.name().to_string())).collect()
+                        value=block
+                        on_change=set_block
+                    />
+
+                    <For
+                        each=move || lift_ids.get()
+                        key=|id| *id
+                        children=move |id| {
+                            let (increment, set_increment) = lift_increment(id);
+                            view! {
+                                <InputField
+                                    label=Signal::derive(move || format!("{} increase per cycle ({})", lift_name(id).get(), unit.get()))
+                                    value=increment
+                                    on_change=set_increment
+                                    step=Signal::derive(move || unit.get().rounding_increment())
+                                />
+                            }
+                        }
+                    />
+
+                    <SelectField
+                        label="Estimated 1RM formula".to_string()
+                        options=E1rmFormula::ALL.iter().map(|formula| (*formula, formula.name().to_string())).collect()
+                        value=e1rm_formula
+                        on_change=set_e1rm_formula
+                    />
+                </div>
+
+                <div class="settings-grid">
+                    <SelectField
+                        label="Warm-up sets".to_string()
+                        options=std::iter::once((None, "None".to_string()))
+                            .chain(WarmupRamp::ALL.iter().map(|ramp| (Some(*ramp), ramp.name().to_string())))
+                            .collect()
+                        value=warmup_ramp
+                        on_change=set_warmup_ramp
+                    />
+
+                    <SelectField
+                        label="Top sets".to_string()
+                        options=std::iter::once((None, "AMRAP at fixed percentages".to_string()))
+                            .chain(Autoregulation::OPTIONS.iter().map(|option| (Some(*option), option.name())))
+                            .collect()
+                        value=autoregulation
+                        on_change=set_autoregulation
+                    />
+                </div>
+
+                <div class="settings-grid">
+                    <ToggleField
+                        label="Joker sets after AMRAP top sets".to_string()
+                        checked=use_jokers
+                        on_change=set_use_jokers
+                    />
+
+                    <ToggleField
+                        label="Beyond extensions after the max week single".to_string()
+                        checked=beyond_extensions
+                        on_change=set_beyond_extensions
+                    />
+
+                    {move || (use_jokers.get() || beyond_extensions.get()).then(|| view! {
+                        <InputField
+                            label="Extra sets (at most)".to_string()
+                            value=joker_sets
+                            on_change=set_joker_sets
+                            step=1.0
+                            min=1.0
+                        />
+
+                        <InputField
+                            label="Jump per set (%)".to_string()
+                            value=joker_jump
+                            on_change=set_joker_jump
+                            step=2.5
+                            min=JokerSettings::MIN_JUMP
+                        />
+
+                        <SelectField
+                            label="Stop rule".to_string()
+                            options=StopRule::OPTIONS.iter().map(|rule| (*rule, rule.to_string())).collect()
+                            value=stop_rule
+                            on_change=set_stop_rule
+                        />
+                    })}
+                </div>
+
+                <div class="settings-grid">
+                    <For
+                        each=move || lift_ids.get()
+                        key=|id| *id
+                        children=move |id| {
+                            let (template, set_template) = supplemental_template(id);
+                            let options = std::iter::once((None, "None".to_string()))
+                                .chain(SupplementalTemplate::ALL.iter().map(|template| (Some(*template), template.name().to_string())))
+                                .collect();
+                            view! {
+                                <SelectField
+                                    label=Signal::derive(move || format!("{} supplemental", lift_name(id).get()))
+                                    options=options
+                                    value=template
+                                    on_change=set_template
+                                />
+                            }
+                        }
                     />
+
+                    <div class="supplemental-toggles">
+                        <For
+                            each=move || template_weeks.get()
+                            key=|week| *week
+                            children=move |week| {
+                                let (enabled, set_enabled) = supplemental_week(week);
+                                view! {
+                                    <ToggleField label=format!("Week {}", week) checked=enabled on_change=set_enabled />
+                                }
+                            }
+                        />
+                        <For
+                            each=move || template_days.get()
+                            key=|day| day.clone()
+                            children=move |day| {
+                                let (enabled, set_enabled) = supplemental_day(day.clone());
+                                view! {
+                                    <ToggleField label=day checked=enabled on_change=set_enabled />
+                                }
+                            }
+                        />
+                    </div>
                 </div>
+
+                <PlateInventoryInput inventory=plate_inventory on_change=set_plate_inventory unit=unit />
+
+                <ScheduleInput schedule=schedule on_change=set_schedule lifts=lifts />
+
+                <CalendarInput settings=calendar_settings on_change=set_calendar_settings />
+
+                <AssistanceInput plan=assistance on_change=set_assistance days=template_days unit=unit />
             </div>
             
             {move || {
@@ -113,16 +565,64 @@ pub fn Beyond531Calculator() -> impl IntoView {
                             <div class="program-description">
                                 <h3>"Program Overview"</h3>
                                 <ul>
-                                    <li><strong>"Week 1:"</strong> " Friday top set: 5+ @ 80%"</li>
-                                    <li><strong>"Week 2:"</strong> " Friday top set: 3+ @ 85%"</li>
-                                    <li><strong>"Week 3:"</strong> " Friday top set: 1+ @ 90%"</li>
+                                    <li><strong>"Week 1:"</strong> " Heavy day top set: 5+ @ 80%"</li>
+                                    <li><strong>"Week 2:"</strong> " Heavy day top set: 3+ @ 85%"</li>
+                                    <li><strong>"Week 3:"</strong> " Heavy day top set: 1+ @ 90%"</li>
                                     <li><strong>"Week 4:"</strong> " MAX WEEK: Singles up to 105%"</li>
+                                    {move || match block.get() {
+                                        BlockStructure::MaxWeekOnly => None,
+                                        BlockStructure::DeloadAfterMaxWeek => Some(view! {
+                                            <li><strong>"Week 5:"</strong> " Deload on every training day: 40%, 50%, 60% x5"</li>
+                                        }),
+                                        BlockStructure::SeventhWeekTrainingMaxTest => Some(view! {
+                                            <li><strong>"Week 5:"</strong> " TM test on the heavy day: work up to 5 reps @ 100%"</li>
+                                        }),
+                                        BlockStructure::SeventhWeekDeload => Some(view! {
+                                            <li><strong>"Week 5:"</strong> " 7th-week deload on every training day: 70% x5, 80% x3, 90% x1, 100% x1"</li>
+                                        }),
+                                    }}
                                 </ul>
-                                <p>"Monday sessions use reduced volume for CNS management: 65% on Weeks 1 & 4, 75% on Weeks 2 & 3. Friday sessions follow modified 5/3/1 progression with AMRAP top sets (+ indicates as many reps as possible)."</p>
+                                {move || match block.get() {
+                                    BlockStructure::MaxWeekOnly => None,
+                                    BlockStructure::DeloadAfterMaxWeek => Some(view! {
+                                        <p>"The deload follows every max week, the last cycle's included."</p>
+                                    }),
+                                    BlockStructure::SeventhWeekTrainingMaxTest | BlockStructure::SeventhWeekDeload => Some(view! {
+                                        <p>"The 7th week runs between cycles, so the last cycle ends with its max week. Its percentages are of your Training Max."</p>
+                                    }),
+                                }}
+                                <p>"Volume days (Monday by default) use reduced volume for CNS management: 65% on Weeks 1 & 4, 75% on Weeks 2 & 3. Heavy days (Friday by default) follow modified 5/3/1 progression with AMRAP top sets (+ indicates as many reps as possible). Optional Joker sets climb past the top set in 5–10% jumps until the stop rule says otherwise. Log your AMRAP reps to see an estimated 1RM; the best estimate per lift becomes that lift's 1RM in the next cycle."</p>
+                                <p>"The page address now links to this program, so you can bookmark it or send it to an athlete."</p>
+                                <div class="export-buttons">
+                                    <button class="export-button" on:click=export_calendar>
+                                        "Export calendar (.ics)"
+                                    </button>
+                                    <button class="export-button" on:click=export_json>
+                                        "Download JSON"
+                                    </button>
+                                    <button class="export-button" on:click=export_csv>
+                                        "Download CSV"
+                                    </button>
+                                    <button class="export-button" on:click=export_pdf>
+                                        "Download PDF"
+                                    </button>
+                                </div>
+                                {move || autoregulation.get().map(|autoregulation| view! {
+                                    <p>{format!("Top sets are prescribed as reps @ RPE {} instead of AMRAP. Their weights come from an RPE chart and your current max; log the RPE you reached and later top sets in the cycle adjust to match.", autoregulation.top_set_rpe)}</p>
+                                })}
+                                {move || use_training_max.get().then(|| view! {
+                                    <p>{format!("Percentages are taken from a Training Max of {}% of your 1RM. Max week singles are still based on your true 1RM.", training_max_percent.get())}</p>
+                                })}
                             </div>
                             
                             <div class="results-display">
-                                <TrainingProgramDisplay program=create_memo(move |_| training_program.get().unwrap()) />
+                                <TrainingProgramDisplay
+                                    program=create_memo(move |_| training_program.get().unwrap())
+                                    plate_inventory=plate_inventory
+                                    training_log=training_log
+                                    e1rm_formula=e1rm_formula
+                                    selected_week=selected_week
+                                />
                             </div>
                         </div>
                     }
@@ -132,6 +632,14 @@ pub fn Beyond531Calculator() -> impl IntoView {
                     }
                 }
             }}
+
+            {move || training_program.get().map(|program| view! { <PrintSheet program=program /> })}
         </div>
     }
-}
\ No newline at end of file
+}
+
+/// Programs without a start date begin today.
+fn starting_today(mut state: CalculatorState) -> CalculatorState {
+    state.calendar.start_date.get_or_insert_with(|| Local::now().date_naive());
+    state
+}
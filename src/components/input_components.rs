@@ -1,28 +1,565 @@
 use leptos::*;
+use crate::assistance::{AssistancePick, AssistancePlan, MovementPattern, LIBRARY};
+use chrono::{NaiveDate, NaiveTime};
+use crate::beyond_531::{Lift, LiftCategory, Unit};
+use crate::calculator_state::LiftEntry;
+use crate::calendar::CalendarSettings;
+use crate::plate_calculator::PlateInventory;
+use crate::schedule::{Schedule, Weekday};
+use crate::validation::{parse_number, ValidationError, WeightLimits};
 
+/// A numeric field. Text that doesn't parse is reported inline instead of being passed on;
+/// `error` shows a problem found elsewhere and `on_error` hears about parse errors.
 #[component]
 pub fn InputField(
-    label: String,
-    value: ReadSignal<f64>,
-    on_change: WriteSignal<f64>,
-    #[prop(default = 1.0)] step: f64,
-    #[prop(default = 0.0)] min: f64,
+    #[prop(into)] label: MaybeSignal<String>,
+    #[prop(into)] value: Signal<f64>,
+    #[prop(into)] on_change: SignalSetter<f64>,
+    #[prop(into, default = 1.0.into())] step: MaybeSignal<f64>,
+    #[prop(into, default = 0.0.into())] min: MaybeSignal<f64>,
+    #[prop(optional, into)] error: Signal<Option<ValidationError>>,
+    #[prop(optional, into)] on_error: SignalSetter<Option<ValidationError>>,
 ) -> impl IntoView {
+    let (parse_error, set_parse_error) = create_signal(None::<ValidationError>);
+    let shown_error = move || parse_error.get().or_else(|| error.get());
+
     view! {
-        <div class="input-field">
-            <label>{label}</label>
+        <div class=move || if shown_error().is_some() { "input-field invalid" } else { "input-field" }>
+            <label>{move || label.get()}</label>
             <input
                 type="number"
-                step=step
-                min=min
-                value=move || value.get()
+                step=move || step.get()
+                min=move || min.get()
+                prop:value=move || value.get()
                 on:input=move |ev| {
-                    if let Ok(new_value) = event_target_value(&ev).parse::<f64>() {
-                        leptos::logging::log!("Input changed to: {}", new_value);
-                        on_change.set(new_value);
+                    match parse_number(&event_target_value(&ev)) {
+                        Ok(new_value) => {
+                            set_parse_error.set(None);
+                            on_error.set(None);
+                            // Skip no-op updates so `prop:value` doesn't clobber partial input like "10."
+                            if new_value != value.get_untracked() {
+                                on_change.set(new_value);
+                            }
+                        }
+                        Err(err) => {
+                            set_parse_error.set(Some(err));
+                            on_error.set(Some(err));
+                        }
+                    }
+                }
+            />
+            {move || shown_error().map(|err| view! { <p class="field-error">{err.to_string()}</p> })}
+        </div>
+    }
+}
+
+#[component]
+pub fn TextField(
+    #[prop(into)] label: MaybeSignal<String>,
+    #[prop(into)] value: Signal<String>,
+    #[prop(into)] on_change: SignalSetter<String>,
+) -> impl IntoView {
+    view! {
+        <div class="input-field">
+            <label>{move || label.get()}</label>
+            <input
+                type="text"
+                prop:value=move || value.get()
+                on:input=move |ev| on_change.set(event_target_value(&ev))
+            />
+        </div>
+    }
+}
+
+/// A `type="date"` input; clearing it leaves the value as it was.
+#[component]
+pub fn DateField(
+    #[prop(into)] label: MaybeSignal<String>,
+    #[prop(into)] value: Signal<Option<NaiveDate>>,
+    #[prop(into)] on_change: SignalSetter<NaiveDate>,
+) -> impl IntoView {
+    view! {
+        <div class="input-field">
+            <label>{move || label.get()}</label>
+            <input
+                type="date"
+                prop:value=move || value.get().map(|date| date.format("%Y-%m-%d").to_string()).unwrap_or_default()
+                on:change=move |ev| {
+                    if let Ok(date) = NaiveDate::parse_from_str(&event_target_value(&ev), "%Y-%m-%d") {
+                        on_change.set(date);
                     }
                 }
             />
         </div>
     }
-}
\ No newline at end of file
+}
+
+#[component]
+pub fn ToggleField(
+    label: String,
+    #[prop(into)] checked: Signal<bool>,
+    #[prop(into)] on_change: SignalSetter<bool>,
+) -> impl IntoView {
+    view! {
+        <div class="toggle-field">
+            <label>
+                <input
+                    type="checkbox"
+                    prop:checked=move || checked.get()
+                    on:change=move |ev| on_change.set(event_target_checked(&ev))
+                />
+                {label}
+            </label>
+        </div>
+    }
+}
+
+
+#[component]
+pub fn SelectField<T>(
+    #[prop(into)] label: MaybeSignal<String>,
+    options: Vec<(T, String)>,
+    #[prop(into)] value: Signal<T>,
+    #[prop(into)] on_change: SignalSetter<T>,
+) -> impl IntoView
+where
+    T: Copy + PartialEq + 'static,
+{
+    let values: Vec<T> = options.iter().map(|(option, _)| *option).collect();
+
+    view! {
+        <div class="input-field">
+            <label>{move || label.get()}</label>
+            <select on:change=move |ev| {
+                if let Some(option) = event_target_value(&ev).parse::<usize>().ok().and_then(|index| values.get(index)) {
+                    on_change.set(*option);
+                }
+            }>
+                {options.into_iter().enumerate().map(|(index, (option, name))| view! {
+                    <option value=index.to_string() selected=move || value.get() == option>
+                        {name}
+                    </option>
+                }).collect_view()}
+            </select>
+        </div>
+    }
+}
+
+
+#[component]
+pub fn PlateInventoryInput(
+    #[prop(into)] inventory: Signal<PlateInventory>,
+    #[prop(into)] on_change: SignalSetter<PlateInventory>,
+    #[prop(into)] unit: Signal<Unit>,
+) -> impl IntoView {
+    let update = move |edit: &dyn Fn(&mut PlateInventory)| {
+        let mut updated = inventory.get_untracked();
+        edit(&mut updated);
+        on_change.set(updated);
+    };
+    let bar_weight = Signal::derive(move || inventory.with(|inventory| inventory.bar_weight));
+    let set_bar_weight = SignalSetter::map(move |bar_weight| update(&|inventory| inventory.bar_weight = bar_weight));
+
+    view! {
+        <details class="plate-inventory">
+            <summary>"Bar and plate inventory"</summary>
+            <div class="settings-grid">
+                <InputField
+                    label=Signal::derive(move || format!("Bar weight ({})", unit.get()))
+                    value=bar_weight
+                    on_change=set_bar_weight
+                    step=0.5
+                />
+
+                // The plate sizes only change with the unit, so re-render the list on unit changes only
+                {move || {
+                    let unit = unit.get();
+                    let plate_weights: Vec<f64> = inventory.with_untracked(|inventory| {
+                        inventory.plates.iter().map(|stock| stock.weight).collect()
+                    });
+                    plate_weights.into_iter().enumerate().map(|(index, plate_weight)| {
+                        let count = Signal::derive(move || {
+                            inventory.with(|inventory| inventory.plates.get(index).map_or(0.0, |stock| stock.count as f64))
+                        });
+                        let set_count = SignalSetter::map(move |count: f64| update(&|inventory| {
+                            if let Some(stock) = inventory.plates.get_mut(index) {
+                                stock.count = count.max(0.0) as u32;
+                            }
+                        }));
+                        view! {
+                            <InputField
+                                label=format!("{}{} plates (total)", plate_weight, unit)
+                                value=count
+                                on_change=set_count
+                                step=2.0
+                            />
+                        }
+                    }).collect_view()
+                }}
+            </div>
+        </details>
+    }
+}
+
+
+/// Name, category and 1RM for one entry of the lift list.
+#[component]
+pub fn LiftInput(
+    #[prop(into)] lift: Signal<LiftEntry>,
+    #[prop(into)] on_change: SignalSetter<LiftEntry>,
+    #[prop(into)] unit: Signal<Unit>,
+    #[prop(into)] on_remove: Callback<()>,
+    #[prop(into)] one_rep_max_error: Signal<Option<ValidationError>>,
+    #[prop(into)] on_one_rep_max_error: SignalSetter<Option<ValidationError>>,
+) -> impl IntoView {
+    let update = move |edit: &dyn Fn(&mut LiftEntry)| {
+        let mut updated = lift.get_untracked();
+        edit(&mut updated);
+        on_change.set(updated);
+    };
+    let name = Signal::derive(move || lift.with(|entry| entry.lift.name.clone()));
+    let set_name = SignalSetter::map(move |name: String| update(&|entry| entry.lift.name = name.clone()));
+    let category = Signal::derive(move || lift.with(|entry| entry.lift.category));
+    let set_category = SignalSetter::map(move |category| update(&|entry| entry.lift.category = category));
+    let one_rep_max = Signal::derive(move || lift.with(|entry| entry.one_rep_max));
+    let set_one_rep_max = SignalSetter::map(move |weight| update(&|entry| entry.one_rep_max = weight));
+
+    view! {
+        <div class="lift-input">
+            <TextField label="Lift".to_string() value=name on_change=set_name />
+            <SelectField
+                label="Category".to_string()
+                options=LiftCategory::ALL.iter().map(|category| (*category, category.name().to_string())).collect()
+                value=category
+                on_change=set_category
+            />
+            <InputField
+                label=Signal::derive(move || format!("1RM ({})", unit.get()))
+                value=one_rep_max
+                on_change=set_one_rep_max
+                step=Signal::derive(move || unit.get().rounding_increment())
+                min=Signal::derive(move || WeightLimits::for_unit(unit.get()).minimum)
+                error=one_rep_max_error
+                on_error=on_one_rep_max_error
+            />
+            <button class="remove-button" on:click=move |_| on_remove.call(())>
+                "Remove"
+            </button>
+        </div>
+    }
+}
+
+/// Assistance picks per training day, and the rep range each movement pattern should land in.
+#[component]
+pub fn AssistanceInput(
+    #[prop(into)] plan: Signal<AssistancePlan>,
+    #[prop(into)] on_change: SignalSetter<AssistancePlan>,
+    #[prop(into)] days: Signal<Vec<String>>,
+    #[prop(into)] unit: Signal<Unit>,
+) -> impl IntoView {
+    let update = move |edit: &dyn Fn(&mut AssistancePlan)| {
+        let mut updated = plan.get_untracked();
+        edit(&mut updated);
+        on_change.set(updated);
+    };
+
+    view! {
+        <details class="assistance-plan">
+            <summary>"Assistance work"</summary>
+            <div class="settings-grid">
+                {MovementPattern::ALL.into_iter().map(|pattern| {
+                    let min_reps = Signal::derive(move || plan.with(|plan| plan.target_for(pattern).min_reps as f64));
+                    let set_min_reps = SignalSetter::map(move |reps: f64| update(&|plan| {
+                        plan.targets.entry(pattern).or_default().min_reps = reps.max(0.0) as u32;
+                    }));
+                    let max_reps = Signal::derive(move || plan.with(|plan| plan.target_for(pattern).max_reps as f64));
+                    let set_max_reps = SignalSetter::map(move |reps: f64| update(&|plan| {
+                        plan.targets.entry(pattern).or_default().max_reps = reps.max(0.0) as u32;
+                    }));
+                    view! {
+                        <InputField
+                            label=format!("{} reps per session (min)", pattern.name())
+                            value=min_reps
+                            on_change=set_min_reps
+                            step=5.0
+                        />
+                        <InputField
+                            label=format!("{} reps per session (max)", pattern.name())
+                            value=max_reps
+                            on_change=set_max_reps
+                            step=5.0
+                        />
+                    }
+                }).collect_view()}
+            </div>
+            <For
+                each=move || days.get()
+                key=|day| day.clone()
+                children=move |day| view! {
+                    <AssistanceDayInput day=day plan=plan on_change=on_change unit=unit />
+                }
+            />
+        </details>
+    }
+}
+
+#[component]
+pub fn AssistanceDayInput(
+    day: String,
+    plan: Signal<AssistancePlan>,
+    on_change: SignalSetter<AssistancePlan>,
+    unit: Signal<Unit>,
+) -> impl IntoView {
+    let update = move |edit: &dyn Fn(&mut AssistancePlan)| {
+        let mut updated = plan.get_untracked();
+        edit(&mut updated);
+        on_change.set(updated);
+    };
+    let (choice, set_choice) = create_signal(0);
+
+    // Rows are keyed by position and name, so editing sets or reps keeps the row (and its focus)
+    let picks = {
+        let day = day.clone();
+        move || plan.with(|plan| {
+            plan.picks.iter().enumerate()
+                .filter(|(_, pick)| pick.day == day)
+                .map(|(index, pick)| (index, pick.exercise.clone()))
+                .collect::<Vec<_>>()
+        })
+    };
+    let add_pick = {
+        let day = day.clone();
+        move |_| {
+            if let Some(exercise) = LIBRARY.get(choice.get_untracked()) {
+                let pick = AssistancePick::new(&day, exercise);
+                update(&|plan| plan.picks.push(pick.clone()));
+            }
+        }
+    };
+
+    view! {
+        <div class="assistance-day">
+            <h4>{day}</h4>
+            <For
+                each=picks
+                key=|pick| pick.clone()
+                children=move |(index, name)| {
+                    let field = move |get: fn(&AssistancePick) -> f64, set: fn(&mut AssistancePick, f64)| (
+                        Signal::derive(move || plan.with(|plan| plan.picks.get(index).map_or(0.0, get))),
+                        SignalSetter::map(move |value| update(&|plan| {
+                            if let Some(pick) = plan.picks.get_mut(index) {
+                                set(pick, value);
+                            }
+                        })),
+                    );
+                    let (sets, set_sets) = field(|pick| pick.sets as f64, |pick, sets| pick.sets = sets.max(1.0) as u32);
+                    let (reps, set_reps) = field(|pick| pick.reps as f64, |pick, reps| pick.reps = reps.max(1.0) as u32);
+                    let (weight, set_weight) = field(
+                        |pick| pick.weight.unwrap_or(0.0),
+                        |pick, weight| pick.weight = (weight > 0.0).then_some(weight),
+                    );
+                    view! {
+                        <div class="assistance-pick">
+                            <span class="assistance-name">{name}</span>
+                            <InputField label="Sets".to_string() value=sets on_change=set_sets min=1.0 />
+                            <InputField label="Reps".to_string() value=reps on_change=set_reps min=1.0 />
+                            <InputField
+                                label=Signal::derive(move || format!("Weight ({}, 0 for none)", unit.get()))
+                                value=weight
+                                on_change=set_weight
+                                step=Signal::derive(move || unit.get().rounding_increment())
+                            />
+                            <button
+                                class="remove-button"
+                                on:click=move |_| update(&|plan| {
+                                    if index < plan.picks.len() {
+                                        plan.picks.remove(index);
+                                    }
+                                })
+                            >
+                                "Remove"
+                            </button>
+                        </div>
+                    }
+                }
+            />
+            <div class="assistance-add">
+                <SelectField
+                    label="Exercise".to_string()
+                    options=LIBRARY.iter().enumerate()
+                        .map(|(index, exercise)| (index, format!("{} – {}", exercise.pattern.name(), exercise.name)))
+                        .collect()
+                    value=choice
+                    on_change=set_choice
+                />
+                <button class="add-button" on:click=add_pick>"+ Add"</button>
+            </div>
+        </div>
+    }
+}
+
+/// Training days and the days each lift's volume and heavy work go on.
+#[component]
+pub fn ScheduleInput(
+    #[prop(into)] schedule: Signal<Schedule>,
+    #[prop(into)] on_change: SignalSetter<Schedule>,
+    #[prop(into)] lifts: Signal<Vec<Lift>>,
+) -> impl IntoView {
+    let update = move |edit: &dyn Fn(&mut Schedule)| {
+        let mut updated = schedule.get_untracked();
+        edit(&mut updated);
+        on_change.set(updated);
+    };
+    let days = create_memo(move |_| schedule.with(|schedule| schedule.days.clone()));
+    let day_count = Signal::derive(move || days.with(|days| days.len() as f64));
+    let set_day_count = SignalSetter::map(move |count: f64| update(&|schedule| schedule.set_day_count(count.max(1.0) as usize)));
+    let rotate = Signal::derive(move || schedule.with(|schedule| schedule.rotate));
+    let set_rotate = SignalSetter::map(move |rotate| update(&|schedule| schedule.rotate = rotate));
+
+    view! {
+        <details class="schedule-input">
+            <summary>"Training schedule"</summary>
+            <div class="settings-grid">
+                <InputField
+                    label="Training days per week".to_string()
+                    value=day_count
+                    on_change=set_day_count
+                    step=1.0
+                    min=1.0
+                />
+                <ToggleField
+                    label="Rotate lifts one day later each week".to_string()
+                    checked=rotate
+                    on_change=set_rotate
+                />
+                <button
+                    class="add-button"
+                    on:click=move |_| on_change.set(Schedule::three_day_rotating(&lifts.get_untracked()))
+                >
+                    "Use 3-day rotating layout"
+                </button>
+            </div>
+
+            // Day and lift selects are rebuilt whenever the set of days changes, as their options depend on it
+            {move || {
+                let days = days.get();
+                let day_options: Vec<(Option<usize>, String)> = std::iter::once((None, "Not trained".to_string()))
+                    .chain(days.iter().enumerate().map(|(index, weekday)| (Some(index), format!("Day {} ({})", index + 1, weekday.name()))))
+                    .collect();
+
+                view! {
+                    <div class="settings-grid">
+                        {(0..days.len()).map(|index| {
+                            let weekday = Signal::derive(move || schedule.with(|schedule| schedule.days.get(index).copied().unwrap_or(Weekday::Monday)));
+                            let set_weekday = SignalSetter::map(move |weekday| update(&|schedule| {
+                                if let Some(day) = schedule.days.get_mut(index) {
+                                    *day = weekday;
+                                }
+                                schedule.sort_days();
+                            }));
+                            view! {
+                                <SelectField
+                                    label=format!("Day {}", index + 1)
+                                    options=Weekday::ALL.iter().map(|weekday| (*weekday, weekday.name().to_string())).collect()
+                                    value=weekday
+                                    on_change=set_weekday
+                                />
+                            }
+                        }).collect_view()}
+                    </div>
+                    <div class="settings-grid">
+                        {lifts.get().into_iter().map(|lift| {
+                            let id = lift.id;
+                            let volume_day = Signal::derive(move || schedule.with(|schedule| schedule.lift_days(id).volume_day));
+                            let set_volume_day = SignalSetter::map(move |day| update(&|schedule| {
+                                let mut lift_days = schedule.lift_days(id);
+                                lift_days.volume_day = day;
+                                schedule.lift_days.insert(id, lift_days);
+                            }));
+                            let heavy_day = Signal::derive(move || schedule.with(|schedule| schedule.lift_days(id).heavy_day));
+                            let set_heavy_day = SignalSetter::map(move |day| update(&|schedule| {
+                                let mut lift_days = schedule.lift_days(id);
+                                lift_days.heavy_day = day;
+                                schedule.lift_days.insert(id, lift_days);
+                            }));
+                            view! {
+                                <SelectField
+                                    label=format!("{} volume day", lift.name)
+                                    options=day_options.clone()
+                                    value=volume_day
+                                    on_change=set_volume_day
+                                />
+                                <SelectField
+                                    label=format!("{} heavy day", lift.name)
+                                    options=day_options.clone()
+                                    value=heavy_day
+                                    on_change=set_heavy_day
+                                />
+                            }
+                        }).collect_view()}
+                    </div>
+                }
+            }}
+        </details>
+    }
+}
+
+/// Start date of the program and how sessions appear in an exported calendar.
+#[component]
+pub fn CalendarInput(
+    #[prop(into)] settings: Signal<CalendarSettings>,
+    #[prop(into)] on_change: SignalSetter<CalendarSettings>,
+) -> impl IntoView {
+    let update = move |edit: &dyn Fn(&mut CalendarSettings)| {
+        let mut updated = settings.get_untracked();
+        edit(&mut updated);
+        on_change.set(updated);
+    };
+    let start_date = Signal::derive(move || settings.with(|settings| settings.start_date));
+    let set_start_date = SignalSetter::map(move |date| update(&|settings| settings.start_date = Some(date)));
+    let session_time = Signal::derive(move || settings.with(|settings| settings.session_time));
+    let timed = Signal::derive(move || session_time.get().is_some());
+    let set_timed = SignalSetter::map(move |timed: bool| update(&|settings| {
+        settings.session_time = timed.then(|| NaiveTime::from_hms_opt(18, 0, 0).unwrap_or_default());
+    }));
+    let session_minutes = Signal::derive(move || settings.with(|settings| settings.session_minutes as f64));
+    let set_session_minutes = SignalSetter::map(move |minutes: f64| update(&|settings| {
+        settings.session_minutes = minutes.max(1.0) as u32;
+    }));
+
+    view! {
+        <div class="settings-grid">
+            <DateField
+                label="First training day".to_string()
+                value=start_date
+                on_change=set_start_date
+            />
+            <ToggleField
+                label="Timed calendar events".to_string()
+                checked=timed
+                on_change=set_timed
+            />
+            {move || timed.get().then(|| view! {
+                <div class="input-field">
+                    <label>"Session start"</label>
+                    <input
+                        type="time"
+                        prop:value=move || session_time.get().map(|time| time.format("%H:%M").to_string()).unwrap_or_default()
+                        on:change=move |ev| {
+                            if let Ok(time) = NaiveTime::parse_from_str(&event_target_value(&ev), "%H:%M") {
+                                update(&|settings| settings.session_time = Some(time));
+                            }
+                        }
+                    />
+                </div>
+                <InputField
+                    label="Session length (minutes)".to_string()
+                    value=session_minutes
+                    on_change=set_session_minutes
+                    step=15.0
+                    min=15.0
+                />
+            })}
+        </div>
+    }
+}
@@ -1,7 +1,9 @@
 pub mod input_components;
 pub mod result_components;
+pub mod print_components;
 pub mod beyond_531_component;
 
 pub use input_components::*;
 pub use result_components::*;
+pub use print_components::*;
 pub use beyond_531_component::*;
\ No newline at end of file
@@ -0,0 +1,70 @@
+use leptos::*;
+use crate::beyond_531::*;
+
+/// Every week of the program on one sheet, with boxes to tick off sets and blanks for
+/// AMRAP reps. Hidden on screen and shown in place of the calculator when printing.
+#[component]
+pub fn PrintSheet(program: TrainingProgram) -> impl IntoView {
+    let unit = program.unit;
+
+    view! {
+        <div class="print-sheet">
+            {program.cycles.into_iter().map(|cycle| {
+                let maxes = cycle.one_rep_max.lifts.iter()
+                    .map(|max| format!("{} {}{}", max.lift.name, max.weight, unit))
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+
+                view! {
+                    <section class="print-cycle">
+                        <header class="print-header">
+                            <h1>{format!("Beyond 531 - Cycle {}", cycle.cycle_number)}</h1>
+                            <p>{format!("1RMs: {}", maxes)}</p>
+                        </header>
+                        <div class="print-weeks">
+                            {cycle.weeks.into_iter().map(|week| view! {
+                                <div class="print-week">
+                                    <h2>{week.title()}</h2>
+                                    {week.sessions.into_iter().map(|session| view! {
+                                        <PrintSession session=session unit=unit />
+                                    }).collect_view()}
+                                </div>
+                            }).collect_view()}
+                        </div>
+                    </section>
+                }
+            }).collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn PrintSession(session: Session, unit: Unit) -> impl IntoView {
+    view! {
+        <div class="print-session">
+            <h3>{format!("{} · {}", session.day, session.date.format("%-d %b"))}</h3>
+            <ul>
+                {session.exercises.into_iter().map(|exercise| view! {
+                    <li class="print-set">
+                        <span class="print-checks">
+                            {(0..exercise.sets).map(|_| view! { <span class="print-check"></span> }).collect_view()}
+                        </span>
+                        <span>{exercise.describe(unit)}</span>
+                        {exercise.is_amrap.then(|| view! {
+                            <span class="print-amrap">"reps:" <span class="print-blank"></span></span>
+                        })}
+                        {exercise.rpe.map(|_| view! {
+                            <span class="print-amrap">"RPE:" <span class="print-blank"></span></span>
+                        })}
+                    </li>
+                }).collect_view()}
+                {session.assistance.into_iter().map(|work| view! {
+                    <li class="print-set">
+                        <span class="print-checks"><span class="print-check"></span></span>
+                        <span>{work.describe(unit)}</span>
+                    </li>
+                }).collect_view()}
+            </ul>
+        </div>
+    }
+}
@@ -1,52 +1,123 @@
 use leptos::*;
+use crate::assistance::{volume_by_pattern, AssistanceWork};
 use crate::beyond_531::*;
+use crate::components::ToggleField;
+use crate::estimated_max::{E1rmFormula, SetKey, TrainingLog};
+use crate::plate_calculator::PlateInventory;
+use crate::rpe;
 
 #[component]
-pub fn TrainingProgramDisplay(program: Memo<TrainingProgram>) -> impl IntoView {
-    let (selected_week, set_selected_week) = create_signal(1);
+pub fn TrainingProgramDisplay(
+    program: Memo<TrainingProgram>,
+    #[prop(into)] plate_inventory: Signal<PlateInventory>,
+    training_log: RwSignal<TrainingLog>,
+    #[prop(into)] e1rm_formula: Signal<E1rmFormula>,
+    selected_week: RwSignal<u32>,
+) -> impl IntoView {
+    let (selected_cycle, set_selected_cycle) = create_signal(1);
+    let (show_plates, set_show_plates) = create_signal(false);
+
+    let selected_cycle_data = create_memo(move |_| {
+        program.with(|program| {
+            program.cycles.iter()
+                .find(|cycle| cycle.cycle_number == selected_cycle.get())
+                .or(program.cycles.first())
+                .cloned()
+        })
+    });
 
-    let program_data = program.get();
     let selected_week_data = create_memo(move |_| {
-        program_data.weeks.iter()
-            .find(|week| week.week_number == selected_week.get())
-            .cloned()
+        selected_cycle_data.with(|cycle| {
+            cycle.as_ref()?.weeks.iter()
+                .find(|week| week.week_number == selected_week.get())
+                .cloned()
+        })
     });
 
     view! {
         <div class="training-program-container">
+            {move || (program.with(|program| program.cycles.len()) > 1).then(|| view! {
+                <div class="week-selector">
+                    <h3 class="selector-title">"Select Cycle"</h3>
+                    <div class="week-tabs">
+                        <For
+                            each=move || program.get().cycles
+                            key=|cycle| cycle.cycle_number
+                            children=move |cycle| {
+                                let cycle_num = cycle.cycle_number;
+                                let is_selected = create_memo(move |_| selected_cycle.get() == cycle_num);
+
+                                view! {
+                                    <button
+                                        class=move || format!("week-tab {}", if is_selected.get() { "active" } else { "" })
+                                        on:click=move |_| set_selected_cycle.set(cycle_num)
+                                    >
+                                        {format!("C{}", cycle_num)}
+                                    </button>
+                                }
+                            }
+                        />
+                    </div>
+                    {move || selected_cycle_data.get().map(|cycle| {
+                        let unit = cycle.one_rep_max.unit;
+                        let maxes = cycle.one_rep_max.lifts.iter()
+                            .map(|max| format!("{} {}{}", max.lift.name, max.weight, unit))
+                            .collect::<Vec<_>>()
+                            .join(" · ");
+                        view! { <p class="cycle-maxes">{format!("1RMs this cycle: {}", maxes)}</p> }
+                    })}
+                </div>
+            })}
+
             <div class="week-selector">
                 <h3 class="selector-title">"Select Week"</h3>
                 <div class="week-tabs">
                     <For
-                        each=move || program.get().weeks.clone()
+                        each=move || selected_cycle_data.get().map(|cycle| cycle.weeks).unwrap_or_default()
                         key=|week| week.week_number
                         children=move |week| {
                             let week_num = week.week_number;
                             let is_selected = create_memo(move |_| selected_week.get() == week_num);
-                            let week_title = if week_num == 4 {
-                                "W4".to_string()
-                            } else {
-                                format!("W{}", week_num)
+                            let tab_label = match week.kind.label() {
+                                Some(label) if week.kind != WeekKind::Max => format!("W{} {}", week_num, label),
+                                _ => format!("W{}", week_num),
                             };
 
                             view! {
                                 <button
                                     class=move || format!("week-tab {}", if is_selected.get() { "active" } else { "" })
-                                    on:click=move |_| set_selected_week.set(week_num)
+                                    title=week.title()
+                                    on:click=move |_| selected_week.set(week_num)
                                 >
-                                    {week_title}
+                                    {tab_label}
                                 </button>
                             }
                         }
                     />
                 </div>
+                <ToggleField
+                    label="Show plates per side".to_string()
+                    checked=show_plates
+                    on_change=set_show_plates
+                />
             </div>
 
             <div class="week-display-container">
                 {move || {
                     if let Some(week) = selected_week_data.get() {
                         view! {
-                            <WeekDisplay week=week />
+                            <WeekDisplay
+                                week=week
+                                cycle_number=selected_cycle_data.with(|cycle| cycle.as_ref().map_or(1, |cycle| cycle.cycle_number))
+                                lifts=selected_cycle_data.with(|cycle| cycle.as_ref().map(|cycle| {
+                                    cycle.one_rep_max.lifts.iter().map(|max| max.lift.clone()).collect()
+                                }).unwrap_or_default())
+                                unit=program.with(|program| program.unit)
+                                show_plates=show_plates
+                                plate_inventory=plate_inventory
+                                training_log=training_log
+                                e1rm_formula=e1rm_formula
+                            />
                         }.into_view()
                     } else {
                         view! {
@@ -60,23 +131,55 @@ pub fn TrainingProgramDisplay(program: Memo<TrainingProgram>) -> impl IntoView {
 }
 
 #[component]
-pub fn WeekDisplay(week: Week) -> impl IntoView {
-    let week_title = if week.week_number == 4 {
-        format!("Week {} - MAX WEEK!", week.week_number)
-    } else {
-        format!("Week {}", week.week_number)
-    };
+pub fn WeekDisplay(
+    week: Week,
+    cycle_number: u32,
+    lifts: Vec<Lift>,
+    unit: Unit,
+    show_plates: ReadSignal<bool>,
+    plate_inventory: Signal<PlateInventory>,
+    training_log: RwSignal<TrainingLog>,
+    e1rm_formula: Signal<E1rmFormula>,
+) -> impl IntoView {
+    let week_number = week.week_number;
+    let week_title = week.title();
+
+    let logged_week = week.clone();
+    let best_estimates = create_memo(move |_| {
+        training_log.with(|log| log.best_estimates(cycle_number, [&logged_week], e1rm_formula.get()))
+    });
     
     view! {
         <div class="week">
             <h2 class="week-title">{week_title}</h2>
+            {move || best_estimates.with(|estimates| (!estimates.is_empty()).then(|| {
+                let summary = lifts.iter()
+                    .filter_map(|lift| estimates.get(&lift.id).map(|estimate| format!("{} {:.1}{}", lift.name, estimate, unit)))
+                    .collect::<Vec<_>>()
+                    .join(" · ");
+                view! {
+                    <p class="week-estimates">
+                        {format!("Best e1RM this week ({}): {}", e1rm_formula.get().name(), summary)}
+                    </p>
+                }
+            }))}
             <div class="sessions">
                 <For
-                    each=move || week.sessions.clone()
-                    key=|session| session.day.clone()
-                    children=move |session| {
+                    each=move || week.sessions.clone().into_iter().enumerate()
+                    key=|(index, session)| (*index, session.day.clone())
+                    children=move |(session_index, session)| {
                         view! {
-                            <SessionDisplay session=session />
+                            <SessionDisplay
+                                session=session
+                                cycle_number=cycle_number
+                                week_number=week_number
+                                session_index=session_index
+                                unit=unit
+                                show_plates=show_plates
+                                plate_inventory=plate_inventory
+                                training_log=training_log
+                                e1rm_formula=e1rm_formula
+                            />
                         }
                     }
                 />
@@ -86,36 +189,93 @@ pub fn WeekDisplay(week: Week) -> impl IntoView {
 }
 
 #[component]
-pub fn SessionDisplay(session: Session) -> impl IntoView {
+#[allow(clippy::too_many_arguments)]
+pub fn SessionDisplay(
+    session: Session,
+    cycle_number: u32,
+    week_number: u32,
+    session_index: usize,
+    unit: Unit,
+    show_plates: ReadSignal<bool>,
+    plate_inventory: Signal<PlateInventory>,
+    training_log: RwSignal<TrainingLog>,
+    e1rm_formula: Signal<E1rmFormula>,
+) -> impl IntoView {
+    let set_keys = SetKey::for_session(cycle_number, week_number, session_index, &session);
+    let assistance = session.assistance.clone();
+
     view! {
         <div class="session">
-            <h3 class="session-day">{session.day}</h3>
+            <h3 class="session-day">
+                {session.day}
+                <span class="session-date">{session.date.format("%-d %b %Y").to_string()}</span>
+            </h3>
             <div class="exercises">
                 <For
-                    each=move || session.exercises.clone()
-                    key=|exercise| format!("{}_{}_{}_{}", exercise.name, exercise.sets, exercise.reps, exercise.weight)
-                    children=move |exercise| {
+                    each=move || session.exercises.clone().into_iter().zip(set_keys.clone()).enumerate()
+                    key=|(index, (exercise, _))| format!("{}_{}_{}_{}_{}", index, exercise.name, exercise.sets, exercise.reps, exercise.weight)
+                    children=move |(_, (exercise, set_key))| {
                         view! {
-                            <ExerciseDisplay exercise=exercise />
+                            <ExerciseDisplay
+                                exercise=exercise
+                                set_key=set_key
+                                unit=unit
+                                show_plates=show_plates
+                                plate_inventory=plate_inventory
+                                training_log=training_log
+                                e1rm_formula=e1rm_formula
+                            />
                         }
                     }
                 />
             </div>
+            {(!assistance.is_empty()).then(|| view! { <AssistanceDisplay work=assistance unit=unit /> })}
         </div>
     }
 }
 
 #[component]
-pub fn ExerciseDisplay(exercise: Exercise) -> impl IntoView {
-    let display_text = if exercise.is_amrap && exercise.sets == 1 && exercise.reps == 1 {
-        format!("{}: 1+ @ {}kg ({}%)", exercise.name, exercise.weight, exercise.percentage as u32)
-    } else if exercise.is_amrap {
-        format!("{}: {}x{}+ @ {}kg ({}%)", exercise.name, exercise.sets, exercise.reps, exercise.weight, exercise.percentage as u32)
-    } else if exercise.sets == 1 && exercise.reps == 1 {
-        format!("{}: {}kg ({}%)", exercise.name, exercise.weight, exercise.percentage as u32)
-    } else {
-        format!("{}: {}x{} @ {}kg ({}%)", exercise.name, exercise.sets, exercise.reps, exercise.weight, exercise.percentage as u32)
-    };
+pub fn AssistanceDisplay(work: Vec<AssistanceWork>, unit: Unit) -> impl IntoView {
+    let volume = volume_by_pattern(&work);
+
+    view! {
+        <div class="assistance">
+            <h4 class="assistance-title">"Assistance"</h4>
+            {work.into_iter().map(|work| view! {
+                <div class="assistance-work">{work.describe(unit)}</div>
+            }).collect_view()}
+            <div class="assistance-volume">
+                {volume.into_iter().map(|(pattern, reps, target)| {
+                    let status = if reps < target.min_reps {
+                        "under-target"
+                    } else if reps > target.max_reps {
+                        "over-target"
+                    } else {
+                        "on-target"
+                    };
+                    view! {
+                        <span class=format!("pattern-volume {}", status)>
+                            {format!("{} {} reps (target {}–{})", pattern.name(), reps, target.min_reps, target.max_reps)}
+                        </span>
+                    }
+                }).collect_view()}
+            </div>
+        </div>
+    }
+}
+
+#[component]
+pub fn ExerciseDisplay(
+    exercise: Exercise,
+    set_key: Option<SetKey>,
+    unit: Unit,
+    show_plates: ReadSignal<bool>,
+    plate_inventory: Signal<PlateInventory>,
+    training_log: RwSignal<TrainingLog>,
+    e1rm_formula: Signal<E1rmFormula>,
+) -> impl IntoView {
+    let weight = exercise.weight;
+    let display_text = exercise.describe(unit);
     
     let intensity_class = match exercise.percentage as u32 {
         65 => "intensity-65",
@@ -129,8 +289,134 @@ pub fn ExerciseDisplay(exercise: Exercise) -> impl IntoView {
         105 => "intensity-105",
         _ => "intensity-default",
     };
+
+    let kind_class = match exercise.kind {
+        SetKind::Warmup => " warmup",
+        SetKind::Main => "",
+        SetKind::Joker => " joker",
+        SetKind::Supplemental => " supplemental",
+    };
     
     view! {
-        <div class=format!("exercise {}", intensity_class)>{display_text}</div>
+        <div class=format!("exercise {}{}", intensity_class, kind_class)>
+            {display_text}
+            {exercise.stop_rule.map(|stop_rule| view! {
+                <div class="stop-rule">{stop_rule.to_string()}</div>
+            })}
+            {set_key.filter(|_| exercise.is_amrap).map(|set_key| view! {
+                <AmrapLogInput
+                    weight=weight
+                    set_key=set_key
+                    unit=unit
+                    training_log=training_log
+                    e1rm_formula=e1rm_formula
+                />
+            })}
+            {set_key.filter(|_| exercise.rpe.is_some()).map(|set_key| view! {
+                <RpeLogInput
+                    weight=weight
+                    reps=exercise.reps
+                    set_key=set_key
+                    unit=unit
+                    training_log=training_log
+                />
+            })}
+            {move || show_plates.get().then(|| view! {
+                <PlateBreakdown weight=weight unit=unit plate_inventory=plate_inventory />
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn AmrapLogInput(
+    weight: f64,
+    set_key: SetKey,
+    unit: Unit,
+    training_log: RwSignal<TrainingLog>,
+    e1rm_formula: Signal<E1rmFormula>,
+) -> impl IntoView {
+    let reps = create_memo(move |_| training_log.with(|log| log.reps_for(&set_key)));
+    let estimate = create_memo(move |_| reps.get().and_then(|reps| e1rm_formula.get().estimate(weight, reps)));
+
+    view! {
+        <div class="amrap-log">
+            <label>
+                "Reps done: "
+                <input
+                    type="number"
+                    min=0
+                    step=1
+                    prop:value=move || reps.get().map(|reps| reps.to_string()).unwrap_or_default()
+                    on:input=move |ev| {
+                        let reps = event_target_value(&ev).parse::<u32>().ok();
+                        training_log.update(|log| log.record(set_key, reps));
+                    }
+                />
+            </label>
+            {move || estimate.get().map(|estimate| view! {
+                <span class="e1rm">{format!("e1RM ≈ {:.1}{}", estimate, unit)}</span>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn RpeLogInput(
+    weight: f64,
+    reps: u32,
+    set_key: SetKey,
+    unit: Unit,
+    training_log: RwSignal<TrainingLog>,
+) -> impl IntoView {
+    let rpe = create_memo(move |_| training_log.with(|log| log.rpe_for(&set_key)));
+    let estimate = create_memo(move |_| rpe.get().and_then(|rpe| rpe::estimate_max(weight, reps, rpe)));
+
+    view! {
+        <div class="amrap-log">
+            <label>
+                "RPE reached: "
+                <select on:change=move |ev| {
+                    let rpe = event_target_value(&ev).parse::<f64>().ok();
+                    training_log.update(|log| log.record_rpe(set_key, rpe));
+                }>
+                    <option value="" selected=move || rpe.get().is_none()>"–"</option>
+                    {rpe::RPE_OPTIONS.iter().map(|&option| view! {
+                        <option value=option.to_string() selected=move || rpe.get() == Some(option)>
+                            {option.to_string()}
+                        </option>
+                    }).collect_view()}
+                </select>
+            </label>
+            {move || estimate.get().map(|estimate| view! {
+                <span class="e1rm">{format!("e1RM ≈ {:.1}{}", estimate, unit)}</span>
+            })}
+        </div>
+    }
+}
+
+#[component]
+pub fn PlateBreakdown(weight: f64, unit: Unit, plate_inventory: Signal<PlateInventory>) -> impl IntoView {
+    let load = create_memo(move |_| plate_inventory.with(|inventory| inventory.load(weight)));
+
+    view! {
+        <div class="plate-breakdown">
+            {move || {
+                let load = load.get();
+                let plates = if load.per_side.is_empty() {
+                    "empty bar".to_string()
+                } else {
+                    load.per_side.iter().map(|plate| plate.to_string()).collect::<Vec<_>>().join(" + ")
+                };
+                view! {
+                    <div class="plates-per-side">{format!("Per side: {}", plates)}</div>
+                    {(!load.is_exact()).then(|| view! {
+                        <div class="plate-warning">
+                            {format!("⚠ {}{} can't be loaded exactly; nearest is {}{}", load.target, unit, load.achieved, unit)}
+                        </div>
+                    })}
+                }
+            }}
+        </div>
     }
 }
\ No newline at end of file
@@ -0,0 +1,27 @@
+use wasm_bindgen::{JsCast, JsValue};
+
+/// Hands `contents` to the browser as a file download named `file_name`.
+pub fn download(file_name: &str, mime_type: &str, contents: &[u8]) {
+    if let Err(err) = try_download(file_name, mime_type, contents) {
+        leptos::logging::warn!("Could not download {}: {:?}", file_name, err);
+    }
+}
+
+fn try_download(file_name: &str, mime_type: &str, contents: &[u8]) -> Result<(), JsValue> {
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let parts = js_sys::Array::of1(&js_sys::Uint8Array::from(contents));
+    let blob = web_sys::Blob::new_with_u8_array_sequence_and_options(&parts, &options)?;
+    let url = web_sys::Url::create_object_url_with_blob(&blob)?;
+
+    let link: web_sys::HtmlAnchorElement = document.create_element("a")?.unchecked_into();
+    link.set_href(&url);
+    link.set_download(file_name);
+    link.click();
+
+    web_sys::Url::revoke_object_url(&url)
+}
@@ -2,14 +2,43 @@ use leptos::*;
 use wasm_bindgen::prelude::*;
 
 mod components;
-mod beyond_531;
+mod download;
+pub mod share_link;
+pub mod storage;
+
+pub use beyond531_core::{
+    assistance, beyond_531, block, calculator_state, calendar, estimated_max, joker, pdf, plate_calculator, program_file,
+    program_template, rounding, rpe, saved_state, schedule, supplemental, validation, warmup,
+};
 
 use components::Beyond531Calculator;
 
 #[component]
 pub fn App() -> impl IntoView {
+    let (mut initial_state, initial_log) = storage::load().unwrap_or_default();
+    let mut initial_week = 1;
+    let mut notice = None;
+
+    // A shared link wins over whatever was saved locally
+    match share_link::read_from_location() {
+        Some(Ok(link)) => {
+            // The page writes its own fragment back, so a reload finds the saved state again
+            if !link.matches(&initial_state) {
+                link.apply_to(&mut initial_state);
+            }
+            initial_week = link.week.unwrap_or(1);
+        }
+        Some(Err(err)) => notice = Some(format!("Couldn't open the shared link: {}", err)),
+        None => {}
+    }
+
     view! {
-        <Beyond531Calculator />
+        <Beyond531Calculator
+            initial_state=initial_state
+            initial_log=initial_log
+            initial_week=initial_week
+            notice=notice
+        />
     }
 }
 
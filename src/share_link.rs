@@ -0,0 +1,28 @@
+pub use beyond531_core::share_link::*;
+
+/// Reads the link from the current page URL. `None` means there is no fragment to read.
+pub fn read_from_location() -> Option<Result<SharedLink, ShareLinkError>> {
+    let hash = web_sys::window()?.location().hash().ok()?;
+    (!hash.trim_start_matches('#').is_empty()).then(|| SharedLink::decode(&hash))
+}
+
+/// Replaces the page URL's fragment without adding a history entry; `None` clears it.
+pub fn write_to_location(link: Option<&SharedLink>) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let Ok(history) = window.history() else {
+        return;
+    };
+    let location = window.location();
+    let path = format!(
+        "{}{}",
+        location.pathname().unwrap_or_default(),
+        location.search().unwrap_or_default()
+    );
+    let url = match link {
+        Some(link) => format!("{}#{}", path, link.encode()),
+        None => path,
+    };
+    let _ = history.replace_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(&url));
+}
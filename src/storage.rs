@@ -0,0 +1,40 @@
+use beyond531_core::calculator_state::CalculatorState;
+use beyond531_core::estimated_max::TrainingLog;
+use beyond531_core::saved_state;
+
+const STORAGE_KEY: &str = "beyond531";
+
+/// Restores the last saved calculator inputs and AMRAP log, if any.
+pub fn load() -> Option<(CalculatorState, TrainingLog)> {
+    let json = local_storage()?.get_item(STORAGE_KEY).ok().flatten()?;
+
+    match saved_state::decode(&json) {
+        Ok(saved) => Some(saved),
+        Err(err) => {
+            leptos::logging::warn!("Ignoring saved data: {}", err);
+            None
+        }
+    }
+}
+
+pub fn save(state: &CalculatorState, log: &TrainingLog) {
+    let saved = local_storage().is_some_and(|storage| {
+        storage
+            .set_item(STORAGE_KEY, &saved_state::encode(state, log))
+            .is_ok()
+    });
+
+    if !saved {
+        leptos::logging::warn!("Could not save: localStorage is not available");
+    }
+}
+
+pub fn clear() {
+    if let Some(storage) = local_storage() {
+        let _ = storage.remove_item(STORAGE_KEY);
+    }
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+}